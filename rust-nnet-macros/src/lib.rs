@@ -44,48 +44,15 @@ macro_rules! ffnn {
       }
     }
 
-    impl<P> nnet::prelude::NeuralNet<P> for $ty<P> 
+    impl<P> nnet::prelude::Predictor<P> for $ty<P> 
       where P : nnet::prelude::NeuralNetParameters 
     {
       #[inline(always)] fn dim_input() -> usize { $inputs }
 
       #[inline(always)] fn dim_output() -> usize { $outputs }
 
-      #[inline(always)] fn dim_hidden() -> usize { $hidden }
-
-      #[inline] 
-      fn node(&self, node: nnet::prelude::Node) -> f64 { 
-        match node {
-          nnet::prelude::Node::Input(i) => self.input[i],
-          nnet::prelude::Node::Hidden(i) => self.hidden[i],
-          nnet::prelude::Node::Output(i) => self.output[i],
-          nnet::prelude::Node::WeightInputHidden(i, j) => self.winput[i][j],
-          nnet::prelude::Node::WeightHiddenOutput(i, j) => self.woutput[i][j]
-        }
-      }
-
-      #[inline] 
-      fn node_mut(&mut self, node: nnet::prelude::Node) -> &mut f64 { 
-        match node {
-          nnet::prelude::Node::Input(i) => &mut self.input[i],
-          nnet::prelude::Node::Hidden(i) => &mut self.hidden[i],
-          nnet::prelude::Node::Output(i) => &mut self.output[i],
-          nnet::prelude::Node::WeightInputHidden(i, j) => &mut self.winput[i][j],
-          nnet::prelude::Node::WeightHiddenOutput(i, j) => &mut self.woutput[i][j]
-        }
-      }
-
-      #[inline] 
-      fn layer(&self, layer: nnet::prelude::Layer) -> &[f64] {
-        match layer {
-          nnet::prelude::Layer::Input => self.input.as_ref(),
-          nnet::prelude::Layer::Hidden => self.hidden.as_ref(),
-          nnet::prelude::Layer::Output => self.output.as_ref()
-        }
-      }
-
       fn predict(&mut self, inp: &[f64]) {
-        use nnet::prelude::ActivationFunction;
+        use nnet::prelude::{ActivationFunction, NeuralNet};
 
         assert!(inp.len() == Self::dim_input());
 
@@ -118,6 +85,45 @@ macro_rules! ffnn {
             P::ActivationFunction::activation(self.output[i]);
         }
       }
+
+      #[inline(always)] fn output(&self) -> &[f64] { self.output.as_ref() }
+    }
+
+    impl<P> nnet::prelude::NeuralNet<P> for $ty<P> 
+      where P : nnet::prelude::NeuralNetParameters 
+    {
+      #[inline(always)] fn dim_hidden() -> usize { $hidden }
+
+      #[inline] 
+      fn node(&self, node: nnet::prelude::Node) -> f64 { 
+        match node {
+          nnet::prelude::Node::Input(i) => self.input[i],
+          nnet::prelude::Node::Hidden(i) => self.hidden[i],
+          nnet::prelude::Node::Output(i) => self.output[i],
+          nnet::prelude::Node::WeightInputHidden(i, j) => self.winput[i][j],
+          nnet::prelude::Node::WeightHiddenOutput(i, j) => self.woutput[i][j]
+        }
+      }
+
+      #[inline] 
+      fn node_mut(&mut self, node: nnet::prelude::Node) -> &mut f64 { 
+        match node {
+          nnet::prelude::Node::Input(i) => &mut self.input[i],
+          nnet::prelude::Node::Hidden(i) => &mut self.hidden[i],
+          nnet::prelude::Node::Output(i) => &mut self.output[i],
+          nnet::prelude::Node::WeightInputHidden(i, j) => &mut self.winput[i][j],
+          nnet::prelude::Node::WeightHiddenOutput(i, j) => &mut self.woutput[i][j]
+        }
+      }
+
+      #[inline] 
+      fn layer(&self, layer: nnet::prelude::Layer) -> &[f64] {
+        match layer {
+          nnet::prelude::Layer::Input => self.input.as_ref(),
+          nnet::prelude::Layer::Hidden => self.hidden.as_ref(),
+          nnet::prelude::Layer::Output => self.output.as_ref()
+        }
+      }
     }
   );
   ($ty:ident, $inputs:expr, $hidden:expr, $outputs:expr) => (
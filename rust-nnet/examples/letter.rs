@@ -2,15 +2,17 @@
 //   
 //   * https://takinginitiative.wordpress.com/2008/04/23/basic-neural-network-tutorial-c-implementation-and-source-code/
 //
-// The basic premise is recognizing a single letter ('A') given a large dataset
-// of vectors which represent pixels on a screen. The dataset is found in the 
-// `data/` subfolder in this directory. It is formatted as a CSV, with the 
-// first character being the expected character (one of 26 from the English 
+// The basic premise is recognizing letters given a large dataset of vectors 
+// which represent pixels on a screen. The dataset is found in the `data/` 
+// subfolder in this directory. It is formatted as a CSV, with the first 
+// character being the expected character (one of 26 from the English 
 // alphabet), and the next 16 integers form the input vector.
+//
+// A new neural net is trained to predict all 26 letters. The already trained 
+// neural net in `data/letter.json` only recognizes a single letter ('A').
 
 
 extern crate num;
-extern crate csv;
 extern crate time;
 extern crate nnet;
 extern crate rustc_serialize;
@@ -18,11 +20,13 @@ extern crate rustc_serialize;
 
 use num::Float;
 use time::PreciseTime;
-use csv::{Reader, Result};
+use nnet::dataset::{CsvLoader, Example};
+use nnet::labels::LabelEncoder;
 use nnet::trainer::backpropagation::*;
+use nnet::validation::{Split, threshold};
 use nnet::params::{TanhNeuralNet, LogisticNeuralNet};
-use nnet::prelude::{NeuralNetTrainer, NeuralNet, MomentumConstant, Layer, 
-  LearningRate, TrainingSetMember};
+use nnet::prelude::{NeuralNetTrainer, NeuralNet, Predictor, MomentumConstant, 
+  Layer, LearningRate, TrainingSetMember};
 
 
 // Input  = 16
@@ -31,6 +35,12 @@ use nnet::prelude::{NeuralNetTrainer, NeuralNet, MomentumConstant, Layer,
 ffnn!([derive(RustcEncodable, RustcDecodable)]; LetterNeuralNet, 16, 8, 1);
 
 
+// Input  = 16
+// Hidden = 32
+// Output = 26
+ffnn!(AlphabetNeuralNet, 16, 32, 26);
+
+
 struct MyTrainerParams;
 
 impl MomentumConstant for MyTrainerParams {
@@ -42,85 +52,116 @@ impl LearningRate for MyTrainerParams {
 }
 
 
-/// Training set example. Expected is 0 or 1. 1 if the letter is 'A', and 0 
-/// otherwise.
-///
-#[derive(RustcDecodable)]
-struct LetterData {
-  expected: [f64; 1], 
-  input: [f64; 16]
-}
-
-impl TrainingSetMember for LetterData {
-  #[inline(always)] fn input(&self) -> &[f64] { self.input.as_ref() }
-  #[inline(always)] fn expected(&self) -> &[f64] { self.expected.as_ref() }
-}
-
-
 fn main() {
   // Change this flag to `true`, if you want to load an already trained 
   // neural network. The training process takes a bit.
   let use_json = false;
 
-  // Read the data and transform it into a vector of `LetterData` objects.
   let data = include_str!("data/letter-recognition.data");
-  let rows = Reader::from_string(data)
-    .has_headers(false)
-    .decode()
-    .map(|decoded: Result<(char, _)>| {
-      match decoded {
-        Ok((c, input)) => {
-          let is_a = if c == 'A' { 1f64 } else { 0f64 };
-          LetterData { expected: [is_a; 1], input: input }
-        }
-        Err(e) => panic!("unrecognzed data: {:?}", e)
-      }
-    })
-    .collect::<Vec<LetterData>>();
 
-  let mut nn: LetterNeuralNet<TanhNeuralNet> = if use_json {
+  if use_json {
+    // Read the data into a dataset. The first column is the letter, and the 
+    // expected result is 1 if the letter is 'A', and 0 otherwise.
+    let rows = CsvLoader::new()
+      .label_columns(&[0])
+      .map_labels(|labels| vec![if labels[0] == "A" { 1f64 } else { 0f64 }])
+      .from_string(data)
+      .unwrap();
+
+    println!("found {:?} examples", rows.len());
+
     let json = include_str!("data/letter.json");
-    ::rustc_serialize::json::decode(json).unwrap()
-  } else {
-    LetterNeuralNet::new()
-  };
-  
-  // Use the first 2/3's of the dataset as the training set.
-  let tset = 2 * rows.len() / 3; 
+    let mut nn: LetterNeuralNet<TanhNeuralNet> = 
+      ::rustc_serialize::json::decode(json).unwrap();
+
+    let mut failed_predictions = 0;
+
+    for (i, x) in rows.iter().enumerate() {
+      nn.predict(x.input());
+
+      let prediction = nn.layer(Layer::Output);
 
-  println!("found {:?} examples", rows.len());
-  println!("using {:?} examples to train", tset);
+      if prediction[0].round() as usize != x.expected()[0].round() as usize {
+        println!(
+          "{:?}  | predicted = {:?} / expected = {:?}", 
+          i, 
+          prediction[0].round() as usize,
+          x.expected()[0].round() as usize);
+
+        failed_predictions += 1;
+      }
+    }
+
+    println!("failed = {:?} / total = {:?}", failed_predictions, rows.len());
+  } else {
+    // Read the data into a dataset. The first column is the letter, which is 
+    // one-hot encoded in the range of the tanh activation function.
+    let rows = CsvLoader::new()
+      .label_columns(&[0])
+      .one_hot_for::<TanhNeuralNet>()
+      .from_string(data)
+      .unwrap();
+    let letters = &rows.encoders()[0];
+
+    println!("found {:?} examples of {:?} letters", rows.len(), letters.len());
+
+    // Hold out a fifth of the examples for validation, and another fifth 
+    // for testing. The split is stratified, so each part has the same 
+    // proportion of each letter as the dataset.
+    let split = Split::stratified(
+      &rows, 0.2f64, 0.2f64, threshold::<TanhNeuralNet>(), 0);
+    let train = split.train();
+    let validation = split.validation();
+    let test = split.test();
+
+    println!("using {:?} examples to train", train.len());
 
-  if !use_json {
     let start = PreciseTime::now();
-    
-    for epoch in parallel
-      ::BatchEpochTrainer::<_, _, MyTrainerParams, _>
-      ::with_epochs(&mut nn, &rows[0..tset], 500)
-    {
-      println!("  * Completed EPOCH {}", epoch);
+    let mut nn = AlphabetNeuralNet::<TanhNeuralNet>::new();
+
+    // Train the neural net 10 epochs at a time, and check its accuracy on 
+    // the validation examples in between.
+    for round in 0..5 {
+      for epoch in SeqEpochTrainer::<_, _, MyTrainerParams, _>
+        ::with_epochs(&mut nn, &train, 10)
+      {
+        println!("  * Completed EPOCH {}", round * 10 + epoch);
+      }
+
+      println!("validation accuracy = {:?}", accuracy(&mut nn, letters, &validation));
     }
 
     println!("took = {:?} ms", start.to(PreciseTime::now()).num_milliseconds());
-  }
 
-  let mut failed_predictions = 0;
+    // Decode the predictions of the first few test examples into letters.
+    for x in test.iter().take(5) {
+      nn.predict(x.input());
 
-  for (i, x) in rows.iter().enumerate() {
-    nn.predict(&x.input);
+      let (predicted, score) = letters.decode(nn.layer(Layer::Output));
+      let (expected, _) = letters.decode(x.expected());
 
-    let prediction = nn.layer(Layer::Output);
+      println!("predicted = {} ({:.3}) / expected = {}", predicted, score, expected);
+    }
+
+    println!("test accuracy = {:?}", accuracy(&mut nn, letters, &test));
+  }
+}
 
-    if prediction[0].round() as usize != x.expected[0].round() as usize {
-      println!(
-        "{:?}  | predicted = {:?} / expected = {:?}", 
-        i, 
-        prediction[0].round() as usize,
-        x.expected[0].round() as usize);
 
-      failed_predictions += 1;
+/// Returns the fraction of examples whose letter is predicted correctly.
+///
+fn accuracy<N>(nn: &mut N, letters: &LabelEncoder<String>, xs: &[&Example]) -> f64
+  where N : NeuralNet<TanhNeuralNet>
+{
+  let mut correct = 0;
+
+  for x in xs.iter() {
+    nn.predict(x.input());
+
+    if letters.decode(nn.layer(Layer::Output)).0 == letters.decode(x.expected()).0 {
+      correct += 1;
     }
   }
 
-  println!("failed = {:?} / total = {:?}", failed_predictions, rows.len());
-}
\ No newline at end of file
+  correct as f64 / xs.len() as f64
+}
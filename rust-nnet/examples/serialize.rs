@@ -5,7 +5,7 @@ extern crate rustc_serialize;
 use rustc_serialize::json;
 use rustc_serialize::{Decodable, Decoder};
 use nnet::params::{TanhNeuralNet, LogisticNeuralNet};
-use nnet::prelude::{NeuralNet, Layer};
+use nnet::prelude::{NeuralNet, Predictor, Layer};
 
 /// The `ffnn!` macro can take in any number of meta arguments as its first 
 /// parameter. For this structure, we are automatically deriving 
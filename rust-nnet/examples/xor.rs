@@ -5,8 +5,8 @@ extern crate nnet;
 use time::PreciseTime;
 use nnet::trainer::backpropagation::*;
 use nnet::params::{TanhNeuralNet, LogisticNeuralNet, CEFunction};
-use nnet::prelude::{NeuralNetTrainer, NeuralNet, MomentumConstant, Layer, 
-  LearningRate, TrainerParametersWithErrorFunction};
+use nnet::prelude::{NeuralNetTrainer, NeuralNet, Predictor, MomentumConstant, 
+  Layer, LearningRate, TrainerParametersWithErrorFunction};
 
 
 ffnn!(XORNeuralNet, 2, 3, 1);
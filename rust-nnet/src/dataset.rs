@@ -0,0 +1,349 @@
+use std::error::Error;
+use std::fmt;
+use std::io::Read;
+use std::ops::Deref;
+use std::path::Path;
+
+use csv;
+use labels::LabelEncoder;
+use prelude::*;
+
+
+/// A member of a dataset.
+///
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Example {
+  input: Vec<f64>,
+  expected: Vec<f64>
+}
+
+impl Example {
+  /// Creates a new example.
+  ///
+  #[inline(always)]
+  pub fn new(input: Vec<f64>, expected: Vec<f64>) -> Example {
+    Example { input: input, expected: expected }
+  }
+}
+
+impl TrainingSetMember for Example {
+  #[inline(always)] fn input(&self) -> &[f64] { &self.input }
+  #[inline(always)] fn expected(&self) -> &[f64] { &self.expected }
+}
+
+
+/// Errors that can occur while loading a dataset.
+///
+#[derive(Debug)]
+pub enum DatasetError {
+  /// The CSV couldn't be read.
+  ///
+  Csv(csv::Error),
+
+  /// A row is missing a label column.
+  ///
+  MissingColumn { 
+    #[allow(missing_docs)] row: usize, 
+    #[allow(missing_docs)] column: usize 
+  },
+
+  /// A value that should be numeric couldn't be parsed.
+  ///
+  Parse { 
+    #[allow(missing_docs)] row: usize, 
+    #[allow(missing_docs)] column: usize, 
+    #[allow(missing_docs)] value: String 
+  }
+}
+
+impl fmt::Display for DatasetError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      DatasetError::Csv(ref e) => write!(f, "csv error: {}", e),
+      DatasetError::MissingColumn { row, column } => 
+        write!(f, "row {} is missing column {}", row, column),
+      DatasetError::Parse { row, column, ref value } => 
+        write!(f, "row {}, column {}: {:?} is not a number", row, column, value)
+    }
+  }
+}
+
+impl Error for DatasetError {
+  fn description(&self) -> &str {
+    match *self {
+      DatasetError::Csv(_) => "csv error",
+      DatasetError::MissingColumn { .. } => "missing column",
+      DatasetError::Parse { .. } => "value is not a number"
+    }
+  }
+}
+
+impl From<csv::Error> for DatasetError {
+  #[inline(always)] fn from(e: csv::Error) -> DatasetError { DatasetError::Csv(e) }
+}
+
+
+/// How the values of the label columns are turned into expected results.
+///
+enum Labels {
+  Numeric,
+  OneHot(f64, f64),
+  Custom(Box<Fn(&[String]) -> Vec<f64>>)
+}
+
+
+/// Loads a `Dataset` from a CSV. By default, the CSV has no headers, the 
+/// last column is the (numeric) label, and every other column is an input.
+///
+pub struct CsvLoader {
+  headers: bool,
+  delimiter: u8,
+  labels: Option<Vec<usize>>,
+  ignored: Vec<usize>,
+  mapping: Labels
+}
+
+impl CsvLoader {
+  /// Creates a new loader with the default options.
+  ///
+  #[inline(always)]
+  pub fn new() -> CsvLoader {
+    CsvLoader {
+      headers: false,
+      delimiter: b',',
+      labels: None,
+      ignored: Vec::new(),
+      mapping: Labels::Numeric
+    }
+  }
+
+  /// Sets whether the first row is a header row, which is skipped.
+  ///
+  #[inline(always)]
+  pub fn has_headers(mut self, yes: bool) -> CsvLoader {
+    self.headers = yes;
+    self
+  }
+
+  /// Sets the delimiter between columns.
+  ///
+  #[inline(always)]
+  pub fn delimiter(mut self, delimiter: u8) -> CsvLoader {
+    self.delimiter = delimiter;
+    self
+  }
+
+  /// Sets the (zero based) indices of the label columns. Every other column, 
+  /// that isn't ignored, is an input.
+  ///
+  #[inline(always)]
+  pub fn label_columns(mut self, columns: &[usize]) -> CsvLoader {
+    self.labels = Some(columns.to_vec());
+    self
+  }
+
+  /// Sets the (zero based) indices of columns that are neither inputs nor 
+  /// labels.
+  ///
+  #[inline(always)]
+  pub fn ignore_columns(mut self, columns: &[usize]) -> CsvLoader {
+    self.ignored = columns.to_vec();
+    self
+  }
+
+  /// Treats each label column as categorical. Each distinct value of a 
+  /// column becomes a class, and the expected result is the one-hot 
+  /// encoding of the class of each label column, concatenated, with values 
+  /// of 0 and 1. The encoder of each column is available through 
+  /// `Dataset::encoders`.
+  ///
+  #[inline(always)]
+  pub fn one_hot(mut self) -> CsvLoader {
+    self.mapping = Labels::OneHot(0f64, 1f64);
+    self
+  }
+
+  /// Like `one_hot`, but with values in the range of the activation 
+  /// function `A`.
+  ///
+  #[inline(always)]
+  pub fn one_hot_for<A>(mut self) -> CsvLoader where A : BoundedActivationFunction {
+    let (lo, hi) = A::bounds();
+    self.mapping = Labels::OneHot(lo, hi);
+    self
+  }
+
+  /// Maps the values of the label columns of each row to its expected 
+  /// result with a function.
+  ///
+  #[inline(always)]
+  pub fn map_labels<F>(mut self, f: F) -> CsvLoader 
+    where F : Fn(&[String]) -> Vec<f64> + 'static 
+  {
+    self.mapping = Labels::Custom(Box::new(f));
+    self
+  }
+
+  /// Loads a dataset from a CSV file.
+  ///
+  pub fn from_file<P>(&self, path: P) -> Result<Dataset, DatasetError> 
+    where P : AsRef<Path> 
+  {
+    let reader = try!(csv::Reader::from_file(path));
+    self.load(reader)
+  }
+
+  /// Loads a dataset from a string.
+  ///
+  pub fn from_string<S>(&self, data: S) -> Result<Dataset, DatasetError> 
+    where S : Into<String> 
+  {
+    self.load(csv::Reader::from_string(data))
+  }
+
+  /// Loads a dataset from a reader.
+  ///
+  pub fn from_reader<R>(&self, reader: R) -> Result<Dataset, DatasetError> 
+    where R : Read 
+  {
+    self.load(csv::Reader::from_reader(reader))
+  }
+
+  fn load<R>(&self, reader: csv::Reader<R>) -> Result<Dataset, DatasetError> 
+    where R : Read 
+  {
+    let mut reader = reader.has_headers(self.headers).delimiter(self.delimiter);
+    let headers = if self.headers { Some(try!(reader.headers())) } else { None };
+    let mut rows = Vec::new();
+
+    for record in reader.records() {
+      rows.push(try!(record));
+    }
+
+    let width = rows.first().map(|r| r.len()).unwrap_or(0);
+    let labels = match self.labels {
+      Some(ref columns) => columns.clone(),
+      None if width > 0 => vec![width - 1],
+      None => Vec::new()
+    };
+    let inputs: Vec<usize> = (0..width)
+      .filter(|c| !labels.contains(c) && !self.ignored.contains(c))
+      .collect();
+
+    // Fit an encoder to the classes of each label column.
+    let mut encoders = Vec::new();
+
+    if let Labels::OneHot(lo, hi) = self.mapping {
+      for &c in labels.iter() {
+        let mut values = Vec::with_capacity(rows.len());
+
+        for (r, row) in rows.iter().enumerate() {
+          match row.get(c) {
+            Some(v) => values.push(v.clone()),
+            None => return Err(DatasetError::MissingColumn { row: r, column: c })
+          }
+        }
+
+        encoders.push(LabelEncoder::with_range(values, lo, hi));
+      }
+    }
+
+    let mut examples = Vec::with_capacity(rows.len());
+
+    for (r, row) in rows.iter().enumerate() {
+      let mut input = Vec::with_capacity(inputs.len());
+
+      for &c in inputs.iter() {
+        input.push(try!(parse(row, r, c)));
+      }
+
+      let expected = match self.mapping {
+        Labels::Numeric => {
+          let mut expected = Vec::with_capacity(labels.len());
+
+          for &c in labels.iter() { expected.push(try!(parse(row, r, c))); }
+
+          expected
+        }
+        Labels::OneHot(..) => {
+          let mut expected = Vec::new();
+
+          for (k, &c) in labels.iter().enumerate() {
+            expected.extend(encoders[k].encode(&row[c][..]));
+          }
+
+          expected
+        }
+        Labels::Custom(ref f) => {
+          let mut values = Vec::with_capacity(labels.len());
+
+          for &c in labels.iter() {
+            match row.get(c) {
+              Some(v) => values.push(v.clone()),
+              None => return Err(DatasetError::MissingColumn { row: r, column: c })
+            }
+          }
+
+          f(&values)
+        }
+      };
+
+      examples.push(Example::new(input, expected));
+    }
+
+    Ok(Dataset { examples: examples, encoders: encoders, headers: headers })
+  }
+}
+
+
+/// Parses a numeric value of a row.
+///
+fn parse(row: &[String], r: usize, c: usize) -> Result<f64, DatasetError> {
+  match row.get(c) {
+    Some(v) => v.trim().parse::<f64>().map_err(|_| {
+      DatasetError::Parse { row: r, column: c, value: v.clone() }
+    }),
+    None => Err(DatasetError::MissingColumn { row: r, column: c })
+  }
+}
+
+
+/// A collection of examples, that can be sliced into `TrainingSetMember`s 
+/// accepted by every trainer.
+///
+#[derive(Clone, Debug)]
+pub struct Dataset {
+  examples: Vec<Example>,
+  encoders: Vec<LabelEncoder<String>>,
+  headers: Option<Vec<String>>
+}
+
+impl Dataset {
+  /// Creates a dataset from examples.
+  ///
+  #[inline(always)]
+  pub fn new(examples: Vec<Example>) -> Dataset {
+    Dataset { examples: examples, encoders: Vec::new(), headers: None }
+  }
+
+  /// Returns the encoder of each label column, if the labels were one-hot 
+  /// encoded.
+  ///
+  #[inline(always)] 
+  pub fn encoders(&self) -> &[LabelEncoder<String>] { &self.encoders }
+
+  /// Returns the header row, if the CSV had one.
+  ///
+  #[inline(always)] 
+  pub fn headers(&self) -> Option<&[String]> { self.headers.as_ref().map(|h| &h[..]) }
+
+  /// Consumes the dataset, and returns its examples.
+  ///
+  #[inline(always)] pub fn into_examples(self) -> Vec<Example> { self.examples }
+}
+
+impl Deref for Dataset {
+  type Target = [Example];
+
+  #[inline(always)] fn deref(&self) -> &[Example] { &self.examples }
+}
@@ -0,0 +1,182 @@
+use std::f64::consts::PI;
+
+use rand::thread_rng;
+use rand::distributions::IndependentSample;
+use rand::distributions::range::Range;
+
+
+/// Result of taking an action in an environment.
+///
+#[derive(Clone, Debug)]
+pub struct Step {
+  /// Observation of the environment after the action was taken.
+  ///
+  pub observation: Vec<f64>,
+
+  /// Reward for taking the action.
+  ///
+  pub reward: f64,
+
+  /// Whether the episode has ended.
+  ///
+  pub done: bool
+}
+
+
+/// An environment an agent can interact with, in episodes.
+///
+pub trait Environment {
+  /// Returns the number of actions an agent can take.
+  ///
+  fn actions(&self) -> usize;
+
+  /// Starts a new episode, and returns the first observation.
+  ///
+  fn reset(&mut self) -> Vec<f64>;
+
+  /// Takes an action.
+  ///
+  fn step(&mut self, action: usize) -> Step;
+}
+
+
+/// A grid, where the agent starts in the top left corner, and has to reach 
+/// the bottom right corner. The actions are to move up, down, left, or right. 
+/// Moving into an edge leaves the agent in place. Reaching the goal gives a 
+/// reward of 1, and ends the episode. Every other step gives a reward of 0.
+///
+/// Observations are a one-hot encoding of the agent's position.
+///
+#[derive(Clone, Debug)]
+pub struct GridWorld {
+  width: usize,
+  height: usize,
+  x: usize,
+  y: usize
+}
+
+impl GridWorld {
+  /// Creates a new grid.
+  ///
+  /// # Panics
+  ///
+  /// When the grid has fewer than 2 cells.
+  ///
+  pub fn new(width: usize, height: usize) -> GridWorld {
+    if width * height < 2 { panic!("grid should have at least 2 cells") }
+
+    GridWorld { width: width, height: height, x: 0, y: 0 }
+  }
+
+  fn observation(&self) -> Vec<f64> {
+    let mut obs = vec![0f64; self.width * self.height];
+    obs[self.y * self.width + self.x] = 1f64;
+    obs
+  }
+}
+
+impl Environment for GridWorld {
+  #[inline(always)] fn actions(&self) -> usize { 4 }
+
+  fn reset(&mut self) -> Vec<f64> {
+    self.x = 0;
+    self.y = 0;
+    self.observation()
+  }
+
+  fn step(&mut self, action: usize) -> Step {
+    match action {
+      0 if self.y > 0 => self.y -= 1,
+      1 if self.y + 1 < self.height => self.y += 1,
+      2 if self.x > 0 => self.x -= 1,
+      3 if self.x + 1 < self.width => self.x += 1,
+      _ => ()
+    }
+
+    let done = self.x + 1 == self.width && self.y + 1 == self.height;
+
+    Step { 
+      observation: self.observation(), 
+      reward: if done { 1f64 } else { 0f64 }, 
+      done: done 
+    }
+  }
+}
+
+
+/// The cart-pole balancing problem. A pole is attached to a cart moving along 
+/// a track, and the actions are to push the cart left or right. The episode 
+/// ends when the pole falls more than 12 degrees from upright, or the cart 
+/// moves more than 2.4 units from the center.
+///
+/// Every step the pole stays up gives a reward of 0, and the step that ends 
+/// the episode gives a reward of -1, so the discounted return stays within 
+/// the range of a tanh output node.
+///
+/// Observations are the cart's position and velocity, and the pole's angle 
+/// and angular velocity.
+///
+#[derive(Clone, Debug)]
+pub struct CartPole {
+  x: f64,
+  x_dot: f64,
+  theta: f64,
+  theta_dot: f64
+}
+
+impl CartPole {
+  /// Creates a new cart-pole.
+  ///
+  pub fn new() -> CartPole {
+    CartPole { x: 0f64, x_dot: 0f64, theta: 0f64, theta_dot: 0f64 }
+  }
+
+  fn observation(&self) -> Vec<f64> {
+    vec![self.x, self.x_dot, self.theta, self.theta_dot]
+  }
+}
+
+impl Environment for CartPole {
+  #[inline(always)] fn actions(&self) -> usize { 2 }
+
+  fn reset(&mut self) -> Vec<f64> {
+    let range = Range::new(-0.05f64, 0.05f64);
+    let mut rng = thread_rng();
+
+    self.x = range.ind_sample(&mut rng);
+    self.x_dot = range.ind_sample(&mut rng);
+    self.theta = range.ind_sample(&mut rng);
+    self.theta_dot = range.ind_sample(&mut rng);
+    self.observation()
+  }
+
+  fn step(&mut self, action: usize) -> Step {
+    let gravity = 9.8f64;
+    let mcart = 1f64;
+    let mpole = 0.1f64;
+    let length = 0.5f64;
+    let tau = 0.02f64;
+    let force = if action == 1 { 10f64 } else { -10f64 };
+
+    let cos = self.theta.cos();
+    let sin = self.theta.sin();
+    let temp = (force + mpole * length * self.theta_dot.powi(2) * sin) / 
+      (mcart + mpole);
+    let theta_acc = (gravity * sin - cos * temp) / 
+      (length * (4f64 / 3f64 - mpole * cos.powi(2) / (mcart + mpole)));
+    let x_acc = temp - mpole * length * theta_acc * cos / (mcart + mpole);
+
+    self.x += tau * self.x_dot;
+    self.x_dot += tau * x_acc;
+    self.theta += tau * self.theta_dot;
+    self.theta_dot += tau * theta_acc;
+
+    let done = self.x.abs() > 2.4f64 || self.theta.abs() > 12f64 * PI / 180f64;
+
+    Step { 
+      observation: self.observation(), 
+      reward: if done { -1f64 } else { 0f64 }, 
+      done: done 
+    }
+  }
+}
@@ -0,0 +1,138 @@
+use prelude::*;
+
+
+/// Propagates errors at the output layer back to the input layer of a neural 
+/// net. `deltas[k]` is the derivative of some objective with respect to the 
+/// weighted sum of output node `k`. Returns the derivative of the objective 
+/// with respect to each input node (excluding the bias node).
+///
+/// `predict` should be called on the neural net before propagating, so that 
+/// the activations of each layer are set.
+///
+/// # Panics
+///
+/// When the length of `deltas` isn't the dimension of the output layer.
+///
+pub fn backpropagate<P, N>(nn: &N, deltas: &[f64]) -> Vec<f64>
+  where N : NeuralNet<P>,
+        P : NeuralNetParameters
+{
+  assert!(deltas.len() == N::dim_output());
+
+  let ehidden: Vec<f64> = (0..N::dim_hidden())
+    .map(|i| {
+      let wsum = (0..N::dim_output()).fold(
+        0f64,
+        |acc, j| acc + nn.node(Node::WeightHiddenOutput(i, j)) * deltas[j]);
+
+      P::ActivationFunction::derivative_from_output(nn.node(Node::Hidden(i))) * wsum
+    })
+    .collect();
+
+  (0..N::dim_input())
+    .map(|i| {
+      (0..N::dim_hidden()).fold(
+        0f64,
+        |acc, j| acc + nn.node(Node::WeightInputHidden(i, j)) * ehidden[j])
+    })
+    .collect()
+}
+
+
+/// Computes the gradient of output node `k` with respect to the input, 
+/// d(output_k)/d(input), for a given input.
+///
+pub fn input_gradient<P, N>(nn: &mut N, inp: &[f64], k: usize) -> Vec<f64>
+  where N : NeuralNet<P>,
+        P : NeuralNetParameters
+{
+  nn.predict(inp);
+
+  let mut deltas = vec![0f64; N::dim_output()];
+  deltas[k] = P::ActivationFunction::derivative_from_output(nn.node(Node::Output(k)));
+
+  backpropagate(nn, &deltas)
+}
+
+
+/// Computes the gradient of each output node with respect to the input for a 
+/// given input. The `k`th row is d(output_k)/d(input).
+///
+pub fn input_jacobian<P, N>(nn: &mut N, inp: &[f64]) -> Vec<Vec<f64>>
+  where N : NeuralNet<P>,
+        P : NeuralNetParameters
+{
+  (0..N::dim_output()).map(|k| input_gradient(nn, inp, k)).collect()
+}
+
+
+/// Saliency map of output node `k` for an input, using the magnitude of the 
+/// input gradient.
+///
+pub fn vanilla_gradient<P, N>(nn: &mut N, inp: &[f64], k: usize) -> Vec<f64>
+  where N : NeuralNet<P>,
+        P : NeuralNetParameters
+{
+  input_gradient(nn, inp, k).iter().map(|g| g.abs()).collect()
+}
+
+
+/// Attribution of output node `k` to each feature of an input, using the 
+/// input gradient multiplied by the input.
+///
+pub fn gradient_times_input<P, N>(nn: &mut N, inp: &[f64], k: usize) -> Vec<f64>
+  where N : NeuralNet<P>,
+        P : NeuralNetParameters
+{
+  input_gradient(nn, inp, k)
+    .iter()
+    .zip(inp.iter())
+    .map(|(g, x)| g * x)
+    .collect()
+}
+
+
+/// Attribution of output node `k` to each feature of an input, using 
+/// integrated gradients. Gradients are averaged along the straight line 
+/// from `baseline` to the input, using `steps` points (midpoint rule), and 
+/// multiplied by the difference between the input and `baseline`.
+///
+/// # Panics
+///
+/// When `steps` is 0, or when `baseline` and `inp` have different lengths.
+///
+pub fn integrated_gradients<P, N>(
+  nn: &mut N, 
+  inp: &[f64], 
+  baseline: &[f64], 
+  k: usize, 
+  steps: usize
+) -> Vec<f64>
+  where N : NeuralNet<P>,
+        P : NeuralNetParameters
+{
+  if steps == 0 { panic!("steps should be greater than 0") }
+
+  assert!(inp.len() == baseline.len());
+
+  let mut point = vec![0f64; inp.len()];
+  let mut total = vec![0f64; inp.len()];
+
+  for s in 0..steps {
+    let alpha = (s as f64 + 0.5f64) / steps as f64;
+
+    for i in 0..inp.len() {
+      point[i] = baseline[i] + alpha * (inp[i] - baseline[i]);
+    }
+
+    for (t, g) in total.iter_mut().zip(input_gradient(nn, &point, k)) {
+      *t += g;
+    }
+  }
+
+  total
+    .iter()
+    .zip(inp.iter().zip(baseline.iter()))
+    .map(|(t, (x, b))| (x - b) * t / steps as f64)
+    .collect()
+}
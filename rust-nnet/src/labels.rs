@@ -0,0 +1,124 @@
+use std::borrow::Borrow;
+use prelude::*;
+use validation::argmax;
+
+
+/// Maps class labels (such as characters, strings, or integers) to one-hot
+/// expected results, and back. The expected result of a label has the upper
+/// bound of the output activation for its class, and the lower bound for
+/// every other class, so it can be reached by the output layer. Classes are
+/// ordered by label.
+///
+#[derive(Clone, Debug, RustcEncodable, RustcDecodable)]
+pub struct LabelEncoder<L> {
+  classes: Vec<L>,
+  lo: f64,
+  hi: f64
+}
+
+impl<L> LabelEncoder<L> where L : Ord {
+  /// Creates an encoder for the distinct labels, with targets in the range
+  /// of the activation function `A`.
+  ///
+  #[inline]
+  pub fn fit<A, I>(labels: I) -> LabelEncoder<L>
+    where A : BoundedActivationFunction,
+          I : IntoIterator<Item = L>
+  {
+    let (lo, hi) = A::bounds();
+    LabelEncoder::with_range(labels, lo, hi)
+  }
+
+  /// Creates an encoder for the distinct labels, with targets `lo` for
+  /// every other class, and `hi` for the class of a label.
+  ///
+  pub fn with_range<I>(labels: I, lo: f64, hi: f64) -> LabelEncoder<L>
+    where I : IntoIterator<Item = L>
+  {
+    let mut classes: Vec<L> = labels.into_iter().collect();
+
+    classes.sort();
+    classes.dedup();
+
+    LabelEncoder { classes: classes, lo: lo, hi: hi }
+  }
+
+  /// Returns the classes, in the order of the outputs.
+  ///
+  #[inline(always)] pub fn classes(&self) -> &[L] { &self.classes }
+
+  /// Returns the number of classes, which is the number of outputs a neural
+  /// net needs.
+  ///
+  #[inline(always)] pub fn len(&self) -> usize { self.classes.len() }
+
+  /// Returns true if there are no classes.
+  ///
+  #[inline(always)] pub fn is_empty(&self) -> bool { self.classes.is_empty() }
+
+  /// Returns the index of the output of a label, if it is a known class.
+  ///
+  #[inline]
+  pub fn index_of<Q: ?Sized>(&self, label: &Q) -> Option<usize>
+    where L : Borrow<Q>,
+          Q : Ord
+  {
+    self.classes.binary_search_by(|c| c.borrow().cmp(label)).ok()
+  }
+
+  /// Returns the expected result for a label.
+  ///
+  /// # Panics
+  ///
+  /// When the label isn't a known class.
+  ///
+  pub fn encode<Q: ?Sized>(&self, label: &Q) -> Vec<f64>
+    where L : Borrow<Q>,
+          Q : Ord
+  {
+    let i = match self.index_of(label) {
+      Some(i) => i,
+      None => panic!("label should be one of the known classes")
+    };
+
+    (0..self.classes.len()).map(|j| if i == j { self.hi } else { self.lo }).collect()
+  }
+
+  /// Returns the predicted label of the output layer of a neural net, which
+  /// is the class with the largest output, along with that output as its
+  /// score.
+  ///
+  /// # Panics
+  ///
+  /// When the number of outputs doesn't match the number of classes, or
+  /// there are no classes.
+  ///
+  pub fn decode(&self, outputs: &[f64]) -> (&L, f64) {
+    if outputs.len() != self.classes.len() {
+      panic!("outputs should have one value for each class")
+    }
+    if self.classes.is_empty() { panic!("there should be at least 1 class") }
+
+    let max = argmax(outputs);
+
+    (&self.classes[max], outputs[max])
+  }
+
+  /// Returns every class with its output, from the largest output to the
+  /// smallest.
+  ///
+  /// # Panics
+  ///
+  /// When the number of outputs doesn't match the number of classes.
+  ///
+  pub fn ranked(&self, outputs: &[f64]) -> Vec<(&L, f64)> {
+    if outputs.len() != self.classes.len() {
+      panic!("outputs should have one value for each class")
+    }
+
+    let mut ranked: Vec<(&L, f64)> = self.classes.iter().zip(outputs.iter().cloned()).collect();
+
+    ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(::std::cmp::Ordering::Equal));
+    ranked
+  }
+}
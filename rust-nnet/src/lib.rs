@@ -4,12 +4,29 @@
 
 #[macro_use] 
 extern crate log;
+extern crate csv;
 extern crate num;
 extern crate rand;
 extern crate num_cpus;
 extern crate rustc_serialize;
 extern crate scoped_threadpool;
 
+/// Datasets, and loading them from CSV files.
+///
+pub mod dataset;
+
+/// Environments for reinforcement learning.
+///
+pub mod environment;
+
+/// Input gradients of a neural net, and attribution methods built on them.
+///
+pub mod gradient;
+
+/// Encoding class labels as expected results, and decoding predictions.
+///
+pub mod labels;
+
 /// Implemented parameters for neural nets or trainers.
 ///
 pub mod params;
@@ -18,6 +35,18 @@ pub mod params;
 ///
 pub mod prelude;
 
+/// Feature scaling, fitted on a training set.
+///
+pub mod scaling;
+
+/// Hyperparameter search.
+///
+pub mod search;
+
 /// Trainers. See module level documentation for detailed usage. 
 ///
-pub mod trainer;
\ No newline at end of file
+pub mod trainer;
+
+/// Cross-validation of neural nets.
+///
+pub mod validation;
\ No newline at end of file
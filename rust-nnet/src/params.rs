@@ -1,11 +1,17 @@
 use num;
 use num::Float;
+use std::cell::Cell;
 use prelude::*;
 use rand::thread_rng;
 use rand::distributions::IndependentSample;
 use rand::distributions::range::Range;
 
 
+// Activations are clamped this far inside of the range of an activation 
+// function before being inverted, so the bounds don't map to infinity.
+const INVERSE_EPSILON: f64 = 1e-7;
+
+
 /// Default Parameters for a Logistic Neural Net.
 ///
 #[derive(Copy, Clone, RustcEncodable, RustcDecodable)] 
@@ -14,6 +20,19 @@ pub struct LogisticNeuralNet;
 impl ActivationFunction for LogisticNeuralNet {
   #[inline(always)] fn activation(x: f64) -> f64 { 1f64 / (1f64 + (-x).exp()) }
   #[inline(always)] fn derivative(x: f64) -> f64 { x * (1f64 - x) }
+  #[inline(always)] fn derivative_from_output(y: f64) -> f64 { y * (1f64 - y) }
+}
+
+impl InverseActivationFunction for LogisticNeuralNet {
+  #[inline] 
+  fn inverse(y: f64) -> f64 { 
+    let y = y.max(INVERSE_EPSILON).min(1f64 - INVERSE_EPSILON);
+    (y / (1f64 - y)).ln()
+  }
+}
+
+impl BoundedActivationFunction for LogisticNeuralNet {
+  #[inline(always)] fn bounds() -> (f64, f64) { (0f64, 1f64) }
 }
 
 impl NeuralNetParameters for LogisticNeuralNet {
@@ -31,6 +50,19 @@ pub struct TanhNeuralNet;
 impl ActivationFunction for TanhNeuralNet {
   #[inline(always)] fn activation(x: f64) -> f64 { x.tanh() }
   #[inline(always)] fn derivative(x: f64) -> f64 { 1f64 - x.tanh().powi(2) }
+  #[inline(always)] fn derivative_from_output(y: f64) -> f64 { 1f64 - y.powi(2) }
+}
+
+impl InverseActivationFunction for TanhNeuralNet {
+  #[inline] 
+  fn inverse(y: f64) -> f64 { 
+    let y = y.max(-1f64 + INVERSE_EPSILON).min(1f64 - INVERSE_EPSILON);
+    0.5f64 * ((1f64 + y) / (1f64 - y)).ln()
+  }
+}
+
+impl BoundedActivationFunction for TanhNeuralNet {
+  #[inline(always)] fn bounds() -> (f64, f64) { (-1f64, 1f64) }
 }
 
 impl NeuralNetParameters for TanhNeuralNet {
@@ -134,4 +166,70 @@ impl ErrorFunction for CEFunction {
       .fold(0f64, |acc, (act, exp)| { n += 1f64; acc + act.ln() * exp });
     -(1f64 / n) * sum
   }
+}
+
+
+/// Multiplies the temperature by a constant rate each epoch.
+///
+#[derive(Copy, Clone)] pub struct ExponentialCooling(pub f64);
+
+impl CoolingSchedule for ExponentialCooling {
+  #[inline] 
+  fn temperature(&self, initial: f64, epoch: usize) -> f64 { 
+    initial * self.0.powi(epoch as i32) 
+  }
+}
+
+
+/// Lowers the temperature linearly, reaching 0 after a number of epochs.
+///
+#[derive(Copy, Clone)] pub struct LinearCooling(pub usize);
+
+impl CoolingSchedule for LinearCooling {
+  #[inline] 
+  fn temperature(&self, initial: f64, epoch: usize) -> f64 { 
+    initial * (1f64 - epoch as f64 / self.0 as f64).max(0f64) 
+  }
+}
+
+
+/// Lowers the temperature in proportion to `1 / ln(epoch + 2)`.
+///
+#[derive(Copy, Clone)] pub struct LogarithmicCooling;
+
+impl CoolingSchedule for LogarithmicCooling {
+  #[inline] 
+  fn temperature(&self, initial: f64, epoch: usize) -> f64 { 
+    initial * 2f64.ln() / (epoch as f64 + 2f64).ln() 
+  }
+}
+
+
+thread_local!(static DYNAMIC_LRATE: Cell<f64> = Cell::new(0.1f64));
+thread_local!(static DYNAMIC_MOMENTUM: Cell<f64> = Cell::new(0f64));
+
+
+/// Trainer parameters that can be changed at runtime. The values are local 
+/// to each thread, so trainers running on different threads (for example, 
+/// in a hyperparameter search) can use different values. Parallel trainers 
+/// don't see values set on the thread that created them.
+///
+#[derive(Copy, Clone)] pub struct DynamicTrainerParams;
+
+impl DynamicTrainerParams {
+  /// Sets the learning rate for the current thread.
+  ///
+  #[inline] pub fn set_lrate(x: f64) { DYNAMIC_LRATE.with(|c| c.set(x)) }
+
+  /// Sets the momentum constant for the current thread.
+  ///
+  #[inline] pub fn set_momentum(x: f64) { DYNAMIC_MOMENTUM.with(|c| c.set(x)) }
+}
+
+impl LearningRate for DynamicTrainerParams {
+  #[inline] fn lrate() -> f64 { DYNAMIC_LRATE.with(|c| c.get()) }
+}
+
+impl MomentumConstant for DynamicTrainerParams {
+  #[inline] fn momentum() -> f64 { DYNAMIC_MOMENTUM.with(|c| c.get()) }
 }
\ No newline at end of file
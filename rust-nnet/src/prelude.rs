@@ -26,6 +26,393 @@ impl<S> TrainerParametersWithErrorFunction for S
 } 
 
 
+/// Parameters for a Q-learning agent. Every parameter has a default value.
+///
+pub trait QLearningParameters {
+  /// Discount factor for future rewards.
+  ///
+  fn discount() -> f64 { 0.9f64 }
+
+  /// Max number of transitions kept for experience replay. If 0, there is 
+  /// no experience replay, and the agent trains on the latest transition 
+  /// after each step.
+  ///
+  fn replay_capacity() -> usize { 10000 }
+
+  /// Number of transitions replayed after each step.
+  ///
+  fn batch_size() -> usize { 32 }
+
+  /// Number of replayed transitions between updates of the target network. 
+  /// If 0, the target network is updated after every replayed transition.
+  ///
+  fn sync_interval() -> usize { 1000 }
+
+  /// Probability of taking a random action in the first episode.
+  ///
+  fn epsilon_start() -> f64 { 1f64 }
+
+  /// Lower bound of the probability of taking a random action.
+  ///
+  fn epsilon_min() -> f64 { 0.05f64 }
+
+  /// Factor the probability of taking a random action is multiplied by at 
+  /// the end of each episode.
+  ///
+  fn epsilon_decay() -> f64 { 0.99f64 }
+}
+
+
+/// Parameters for a policy gradient trainer. Every parameter has a default 
+/// value.
+///
+pub trait PolicyGradientParameters {
+  /// Discount factor for future rewards.
+  ///
+  fn discount() -> f64 { 0.99f64 }
+
+  /// Temperature of the softmax over the output layer. Lower temperatures 
+  /// let a policy with a bounded activation function become more 
+  /// deterministic.
+  ///
+  fn temperature() -> f64 { 0.1f64 }
+
+  /// Whether to subtract a baseline (the average return of the episode) from 
+  /// the returns, to reduce the variance of the updates.
+  ///
+  fn baseline() -> bool { true }
+}
+
+
+/// Parameters for NeuroEvolution of Augmenting Topologies (NEAT). Every 
+/// parameter has a default value.
+///
+pub trait NeatParameters {
+  /// Number of genomes in each generation.
+  ///
+  fn population() -> usize { 150 }
+
+  /// Coefficient of the number of excess genes in the compatibility distance.
+  ///
+  fn excess_coefficient() -> f64 { 1f64 }
+
+  /// Coefficient of the number of disjoint genes in the compatibility 
+  /// distance.
+  ///
+  fn disjoint_coefficient() -> f64 { 1f64 }
+
+  /// Coefficient of the average weight difference of matching genes in the 
+  /// compatibility distance.
+  ///
+  fn weight_coefficient() -> f64 { 0.4f64 }
+
+  /// Max compatibility distance between genomes of the same species.
+  ///
+  fn compatibility_threshold() -> f64 { 3f64 }
+
+  /// Probability that the weights of an offspring are mutated.
+  ///
+  fn weight_mutation_rate() -> f64 { 0.8f64 }
+
+  /// Standard deviation of the Gaussian noise added to a mutated weight.
+  ///
+  fn weight_perturbation() -> f64 { 0.5f64 }
+
+  /// Probability that a mutated weight is replaced with a new random weight, 
+  /// instead of perturbed.
+  ///
+  fn weight_replace_rate() -> f64 { 0.1f64 }
+
+  /// Probability that a connection is added to an offspring.
+  ///
+  fn add_connection_rate() -> f64 { 0.05f64 }
+
+  /// Probability that a node is added to an offspring.
+  ///
+  fn add_node_rate() -> f64 { 0.03f64 }
+
+  /// Probability that an offspring is produced by crossover, instead of by 
+  /// mutation alone.
+  ///
+  fn crossover_rate() -> f64 { 0.75f64 }
+
+  /// Fraction of each species allowed to reproduce.
+  ///
+  fn survival_threshold() -> f64 { 0.2f64 }
+
+  /// Number of generations a species can go without improving before it is 
+  /// removed.
+  ///
+  fn stagnation_limit() -> usize { 15 }
+}
+
+
+/// Parameters for a genetic algorithm. Every parameter has a default value.
+///
+pub trait GeneticParameters {
+  /// Number of neural nets in each generation.
+  ///
+  fn population() -> usize { 50 }
+
+  /// Number of the fittest neural nets copied unchanged into the next 
+  /// generation.
+  ///
+  fn elitism() -> usize { 2 }
+
+  /// Number of neural nets competing in each tournament to become a parent.
+  ///
+  fn tournament_size() -> usize { 3 }
+
+  /// Probability that an offspring is produced by crossover, instead of 
+  /// being copied from a single parent.
+  ///
+  fn crossover_rate() -> f64 { 0.7f64 }
+
+  /// Probability that each weight of an offspring is mutated.
+  ///
+  fn mutation_rate() -> f64 { 0.1f64 }
+
+  /// Standard deviation of the Gaussian noise added to a mutated weight.
+  ///
+  fn mutation_stddev() -> f64 { 0.5f64 }
+}
+
+
+/// Parameters for particle swarm optimization. Every parameter has a 
+/// default value.
+///
+pub trait SwarmParameters {
+  /// Number of particles in the swarm.
+  ///
+  fn particles() -> usize { 30 }
+
+  /// Fraction of its velocity a particle keeps between iterations.
+  ///
+  fn inertia() -> f64 { 0.729f64 }
+
+  /// Acceleration towards the best position found by the particle itself.
+  ///
+  fn cognitive() -> f64 { 1.49445f64 }
+
+  /// Acceleration towards the best position found by the neighbourhood of 
+  /// the particle.
+  ///
+  fn social() -> f64 { 1.49445f64 }
+
+  /// Max absolute velocity of a particle along each dimension.
+  ///
+  fn max_velocity() -> f64 { 1f64 }
+
+  /// Neighbourhood of each particle.
+  ///
+  fn topology() -> Topology { Topology::Global }
+}
+
+
+/// Parameters for the covariance matrix adaptation evolution strategy 
+/// (CMA-ES). Every parameter has a default value.
+///
+pub trait CmaParameters {
+  /// Number of offspring sampled each generation, given the number of 
+  /// weights.
+  ///
+  fn population(dim: usize) -> usize { 
+    4 + (3f64 * (dim as f64).ln()).floor() as usize 
+  }
+
+  /// Initial step size.
+  ///
+  fn sigma() -> f64 { 0.5f64 }
+
+  /// Max number of restarts.
+  ///
+  fn restarts() -> usize { 9 }
+
+  /// Factor the population grows by on each restart.
+  ///
+  fn population_growth() -> usize { 2 }
+
+  /// Tolerance on the step size and fitness, below which the strategy is 
+  /// restarted.
+  ///
+  fn tolerance() -> f64 { 1e-12f64 }
+}
+
+
+/// Parameters for simulated annealing. Every parameter has a default value.
+///
+pub trait AnnealingParameters {
+  /// Temperature at the first epoch.
+  ///
+  fn initial_temperature() -> f64 { 0.01f64 }
+
+  /// Number of moves proposed at each temperature.
+  ///
+  fn moves() -> usize { 100 }
+
+  /// Probability that each weight is perturbed by a move. At least one 
+  /// weight is always perturbed.
+  ///
+  fn perturbation_rate() -> f64 { 0.1f64 }
+
+  /// Standard deviation of the Gaussian noise added to a perturbed weight.
+  ///
+  fn step_size() -> f64 { 0.3f64 }
+}
+
+
+/// Schedule the temperature is lowered by during simulated annealing. 
+/// Implemented for any `Fn(f64, usize) -> f64`.
+///
+pub trait CoolingSchedule {
+  /// Returns the temperature at an epoch, given the initial temperature.
+  ///
+  fn temperature(&self, initial: f64, epoch: usize) -> f64;
+}
+
+impl<F> CoolingSchedule for F where F : Fn(f64, usize) -> f64 {
+  #[inline(always)] 
+  fn temperature(&self, initial: f64, epoch: usize) -> f64 { self(initial, epoch) }
+}
+
+
+/// Parameters for resilient backpropagation (RPROP). Every parameter has a 
+/// default value.
+///
+pub trait RpropParameters {
+  /// Factor a step size grows by when the sign of its gradient is unchanged.
+  ///
+  fn increase() -> f64 { 1.2f64 }
+
+  /// Factor a step size shrinks by when the sign of its gradient changes.
+  ///
+  fn decrease() -> f64 { 0.5f64 }
+
+  /// Initial step size of each weight.
+  ///
+  fn initial_step() -> f64 { 0.1f64 }
+
+  /// Max step size of a weight.
+  ///
+  fn max_step() -> f64 { 50f64 }
+
+  /// Min step size of a weight.
+  ///
+  fn min_step() -> f64 { 1e-6f64 }
+}
+
+
+/// Parameters for the Levenberg-Marquardt algorithm. Every parameter has a 
+/// default value.
+///
+pub trait LevenbergMarquardtParameters {
+  /// Initial damping factor.
+  ///
+  fn initial_damping() -> f64 { 1e-3f64 }
+
+  /// Factor the damping is multiplied by when a step increases the error, 
+  /// and divided by when a step decreases it.
+  ///
+  fn damping_factor() -> f64 { 10f64 }
+
+  /// Max damping factor. Training stops when no step that decreases the 
+  /// error can be found below it.
+  ///
+  fn max_damping() -> f64 { 1e10f64 }
+}
+
+
+/// Parameters for Quickprop. Every parameter has a default value.
+///
+pub trait QuickpropParameters {
+  /// Learning rate of the gradient descent term, applied to the gradient 
+  /// averaged over the training set.
+  ///
+  fn epsilon() -> f64 { 0.7f64 }
+
+  /// Max growth factor of a step, relative to the previous step.
+  ///
+  fn max_growth() -> f64 { 1.75f64 }
+
+  /// Weight decay added to the slope of each weight.
+  ///
+  fn decay() -> f64 { -1e-4f64 }
+}
+
+
+/// Parameters for scaled conjugate gradient. Every parameter has a default 
+/// value.
+///
+pub trait ConjugateGradientParameters {
+  /// Distance used to approximate the second order information along the 
+  /// search direction.
+  ///
+  fn sigma() -> f64 { 5e-5f64 }
+
+  /// Initial scale of the model trust region.
+  ///
+  fn lambda() -> f64 { 5e-7f64 }
+}
+
+
+/// Parameters for L-BFGS. Every parameter has a default value.
+///
+pub trait LbfgsParameters {
+  /// Number of previous steps used to approximate the inverse Hessian.
+  ///
+  fn memory() -> usize { 10 }
+
+  /// Sufficient decrease constant of the (Armijo) line search.
+  ///
+  fn armijo() -> f64 { 1e-4f64 }
+
+  /// Factor the step length shrinks by during the line search.
+  ///
+  fn backtrack() -> f64 { 0.5f64 }
+
+  /// Max number of step lengths tried by the line search.
+  ///
+  fn line_search_steps() -> usize { 20 }
+}
+
+
+/// Parameters for Cascade-Correlation. Every parameter has a default value.
+///
+pub trait CascadeParameters {
+  /// Max number of hidden units added to the network.
+  ///
+  fn max_hidden() -> usize { 32 }
+
+  /// Number of candidate units trained before each unit is added.
+  ///
+  fn candidates() -> usize { 8 }
+
+  /// Number of epochs the output weights are trained for after each unit 
+  /// is added.
+  ///
+  fn output_epochs() -> usize { 200 }
+
+  /// Number of epochs each candidate unit is trained for.
+  ///
+  fn candidate_epochs() -> usize { 200 }
+
+  /// Mean squared error, below which no more units are added.
+  ///
+  fn target_error() -> f64 { 1e-3f64 }
+}
+
+
+/// Parameters for an Extreme Learning Machine. Every parameter has a 
+/// default value.
+///
+pub trait ElmParameters {
+  /// Ridge regularization added to the diagonal of the least squares 
+  /// problem.
+  ///
+  fn regularization() -> f64 { 1e-3f64 }
+}
+
+
 /// Function to calculate error during training.
 ///  
 pub trait ErrorFunction {
@@ -54,6 +441,29 @@ pub trait NeuralNetTrainer : Iterator {
 }
 
 
+/// Fitness of a neural net for gradient-free trainers, where a higher 
+/// fitness is better. Implemented for any `Fn(&mut N) -> f64`.
+///
+pub trait Fitness<N> {
+  /// Computes the fitness of a neural net.
+  ///
+  fn fitness(&self, nn: &mut N) -> f64;
+}
+
+impl<N, F> Fitness<N> for F where F : Fn(&mut N) -> f64 {
+  #[inline(always)] fn fitness(&self, nn: &mut N) -> f64 { self(nn) }
+}
+
+
+/// Dimensions of the input and output layers of a neural net, whose hidden 
+/// nodes are only known at runtime.
+///
+pub trait Dimensions {
+  #[allow(missing_docs)] fn inputs() -> usize;
+  #[allow(missing_docs)] fn outputs() -> usize;
+}
+
+
 /// Layers
 ///
 pub enum Layer {
@@ -74,9 +484,32 @@ pub enum Node {
 }
 
 
-/// A single-layer neural network.
+/// Neighbourhood topology of a particle swarm.
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Topology {
+  /// Every particle is a neighbour of every other particle.
+  ///
+  Global,
+
+  /// Particles are arranged in a ring, and are neighbours of the particles 
+  /// within the given distance of them.
+  ///
+  Ring(usize)
+}
+
+
+/// A network that predicts an output for an input. Networks whose weights 
+/// don't form layers (such as those grown by NEAT or Cascade-Correlation) 
+/// only implement this trait, and can't be used with trainers that need the 
+/// layered weights of a `NeuralNet`.
 ///
-pub trait NeuralNet<P> where P : NeuralNetParameters {
+/// `dim_input`, `dim_output`, `predict`, and `output` used to be methods of 
+/// `NeuralNet`, which now requires this trait. Code that imports 
+/// `NeuralNet` by name (rather than through `prelude::*`) needs to import 
+/// `Predictor` as well to call them.
+///
+pub trait Predictor<P> where P : NeuralNetParameters {
   /// Returns the dimensions of the input layer.
   ///
   fn dim_input() -> usize;
@@ -85,6 +518,22 @@ pub trait NeuralNet<P> where P : NeuralNetParameters {
   ///
   fn dim_output() -> usize;
 
+  /// Computes the predicted value for a given input and stores it 
+  /// internally. The prediction can be retrieved using `output`. 
+  /// The reason `predict` doesn't return the prediction, is because it 
+  /// requires a mutable borrow on `self`.
+  ///
+  fn predict(&mut self, inp: &[f64]);
+
+  /// Returns the last prediction.
+  ///
+  fn output(&self) -> &[f64];
+}
+
+
+/// A single-layer neural network.
+///
+pub trait NeuralNet<P> : Predictor<P> where P : NeuralNetParameters {
   /// Returns the dimensions of the hidden layer.
   ///
   fn dim_hidden() -> usize;
@@ -100,13 +549,6 @@ pub trait NeuralNet<P> where P : NeuralNetParameters {
   /// Returns the specified layer.
   ///
   fn layer(&self, layer: Layer) -> &[f64];
-
-  /// Computes the predicted value for a given input and stores it 
-  /// internally. The prediction can be retrieved using `layer`. 
-  /// The reason `predict` doesn't return the prediction, is because it 
-  /// requires a mutable borrow on `self`.
-  ///
-  fn predict(&mut self, inp: &[f64]);
 }
 
 
@@ -129,6 +571,71 @@ pub trait MomentumConstant {
 pub trait ActivationFunction {
   #[allow(missing_docs)] fn activation(x: f64) -> f64;
   #[allow(missing_docs)] fn derivative(x: f64) -> f64;
+
+  /// Returns the derivative of the activation, given the activation itself 
+  /// (the value stored in a node) rather than the weighted sum.
+  ///
+  /// Defaults to `derivative(y)`, which is only correct when `derivative` 
+  /// is written in terms of the activation (as it is for the logistic 
+  /// function). Activation functions whose `derivative` takes the weighted 
+  /// sum (such as tanh) should override it.
+  ///
+  #[inline(always)] fn derivative_from_output(y: f64) -> f64 { Self::derivative(y) }
+}
+
+
+/// Activation function that can be inverted, to recover the weighted sum 
+/// of a node from its activation.
+///
+pub trait InverseActivationFunction : ActivationFunction {
+  #[allow(missing_docs)] fn inverse(y: f64) -> f64;
+}
+
+
+/// Activation function with a bounded range. Targets for the output layer, 
+/// such as one-hot encoded classes, should be within these bounds.
+///
+pub trait BoundedActivationFunction : ActivationFunction {
+  /// Returns the lower and upper bounds of the activation.
+  ///
+  fn bounds() -> (f64, f64);
+}
+
+
+/// Noise applied to each member of a training set as it is used by a 
+/// `DenoisingTrainer`. The training set itself isn't modified. 
+///
+/// Noise is only applied to the expected result through `target_stddev`, so 
+/// for denoising-style training (where the expected result is the clean 
+/// input), the neural net learns to reconstruct the clean input from the 
+/// corrupted one. 
+///
+pub trait Noise {
+  /// Standard deviation of the Gaussian noise added to each input.
+  ///
+  fn input_stddev() -> f64 { 0f64 }
+
+  /// Probability that each input is masked (set to 0).
+  ///
+  fn mask_probability() -> f64 { 0f64 }
+
+  /// Standard deviation of the Gaussian noise added to each expected value.
+  ///
+  fn target_stddev() -> f64 { 0f64 }
+}
+
+
+/// Parameters for a `DenoisingTrainer`, which are trainer parameters with 
+/// the noise to apply to each member of the training set:
+///
+/// ```ignore
+/// impl DenoisingParameters for MyTrainerParams {
+///   type Noise = MyNoise;
+/// }
+/// ```
+///
+pub trait DenoisingParameters : TrainerParameters {
+  #[allow(missing_docs)] type Noise : Noise;
 }
 
 
@@ -171,4 +678,9 @@ impl<'a> TrainingSetMember for (&'a [f64], &'a [f64]) {
 impl TrainingSetMember for (Vec<f64>, Vec<f64>) {
   fn expected(&self) -> &[f64] { &self.1[..] }
   fn input(&self) -> &[f64] { &self.0[..] }
+}
+
+impl<'a, T> TrainingSetMember for &'a T where T : TrainingSetMember {
+  fn expected(&self) -> &[f64] { (**self).expected() }
+  fn input(&self) -> &[f64] { (**self).input() }
 }
\ No newline at end of file
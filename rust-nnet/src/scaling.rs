@@ -0,0 +1,330 @@
+use dataset::Example;
+use prelude::*;
+
+
+/// A transformation of feature vectors (inputs or expected results), fitted 
+/// on a training set.
+///
+pub trait Scaler : Sized {
+  /// Fits the scaler to rows of features. Every row should have the same 
+  /// length.
+  ///
+  fn fit<'a, I>(rows: I) -> Self where I : Iterator<Item = &'a [f64]>;
+
+  /// Transforms a row of features in place.
+  ///
+  fn transform(&self, x: &mut [f64]);
+
+  /// Reverts the transformation of a row of features in place.
+  ///
+  fn inverse_transform(&self, x: &mut [f64]);
+
+  /// Fits the scaler to the inputs of a training set.
+  ///
+  #[inline]
+  fn fit_inputs<T>(tset: &[T]) -> Self where T : TrainingSetMember {
+    Self::fit(tset.iter().map(|m| m.input()))
+  }
+
+  /// Fits the scaler to the expected results of a training set.
+  ///
+  #[inline]
+  fn fit_targets<T>(tset: &[T]) -> Self where T : TrainingSetMember {
+    Self::fit(tset.iter().map(|m| m.expected()))
+  }
+}
+
+
+/// Returns the values of each column of a set of rows.
+///
+fn columns<'a, I>(rows: I) -> Vec<Vec<f64>> where I : Iterator<Item = &'a [f64]> {
+  let mut columns: Vec<Vec<f64>> = Vec::new();
+
+  for row in rows {
+    if columns.is_empty() { 
+      columns = row.iter().map(|_| Vec::new()).collect(); 
+    }
+
+    for (c, &x) in columns.iter_mut().zip(row.iter()) { c.push(x); }
+  }
+
+  columns
+}
+
+
+/// Returns 1 in place of a scale of 0, so constant features are only 
+/// shifted.
+///
+#[inline(always)]
+fn nonzero(scale: f64) -> f64 { if scale == 0f64 { 1f64 } else { scale } }
+
+
+/// Leaves features unchanged.
+///
+#[derive(Copy, Clone, Debug, RustcEncodable, RustcDecodable)]
+pub struct IdentityScaler;
+
+impl Scaler for IdentityScaler {
+  #[inline(always)] 
+  fn fit<'a, I>(_: I) -> Self where I : Iterator<Item = &'a [f64]> { IdentityScaler }
+
+  #[inline(always)] fn transform(&self, _: &mut [f64]) { }
+
+  #[inline(always)] fn inverse_transform(&self, _: &mut [f64]) { }
+}
+
+
+/// Scales each feature linearly to a range, [0, 1] by default, given the min 
+/// and max of the feature in the training set.
+///
+#[derive(Clone, Debug, RustcEncodable, RustcDecodable)]
+pub struct MinMaxScaler {
+  min: Vec<f64>,
+  max: Vec<f64>,
+  lo: f64,
+  hi: f64
+}
+
+impl MinMaxScaler {
+  /// Sets the range features are scaled to. For example, [-1, 1] for tanh 
+  /// units.
+  ///
+  #[inline(always)]
+  pub fn with_range(mut self, lo: f64, hi: f64) -> MinMaxScaler {
+    self.lo = lo;
+    self.hi = hi;
+    self
+  }
+}
+
+impl Scaler for MinMaxScaler {
+  fn fit<'a, I>(rows: I) -> Self where I : Iterator<Item = &'a [f64]> {
+    let columns = columns(rows);
+
+    MinMaxScaler {
+      min: columns
+        .iter()
+        .map(|c| c.iter().fold(::std::f64::INFINITY, |acc, &x| acc.min(x)))
+        .collect(),
+      max: columns
+        .iter()
+        .map(|c| c.iter().fold(::std::f64::NEG_INFINITY, |acc, &x| acc.max(x)))
+        .collect(),
+      lo: 0f64,
+      hi: 1f64
+    }
+  }
+
+  fn transform(&self, x: &mut [f64]) {
+    for i in 0..x.len() {
+      let scale = nonzero(self.max[i] - self.min[i]);
+      x[i] = self.lo + (x[i] - self.min[i]) / scale * (self.hi - self.lo);
+    }
+  }
+
+  fn inverse_transform(&self, x: &mut [f64]) {
+    for i in 0..x.len() {
+      let scale = nonzero(self.max[i] - self.min[i]);
+      x[i] = self.min[i] + (x[i] - self.lo) / (self.hi - self.lo) * scale;
+    }
+  }
+}
+
+
+/// Standardizes each feature to a mean of 0 and a standard deviation of 1 
+/// (z-score), given the mean and standard deviation of the feature in the 
+/// training set.
+///
+#[derive(Clone, Debug, RustcEncodable, RustcDecodable)]
+pub struct StandardScaler {
+  mean: Vec<f64>,
+  stddev: Vec<f64>
+}
+
+impl Scaler for StandardScaler {
+  fn fit<'a, I>(rows: I) -> Self where I : Iterator<Item = &'a [f64]> {
+    let columns = columns(rows);
+    let mean: Vec<f64> = columns
+      .iter()
+      .map(|c| c.iter().fold(0f64, |acc, x| acc + x) / c.len() as f64)
+      .collect();
+    let stddev = columns
+      .iter()
+      .zip(mean.iter())
+      .map(|(c, m)| {
+        (c.iter().fold(0f64, |acc, x| acc + (x - m) * (x - m)) / c.len() as f64).sqrt()
+      })
+      .collect();
+
+    StandardScaler { mean: mean, stddev: stddev }
+  }
+
+  fn transform(&self, x: &mut [f64]) {
+    for i in 0..x.len() { x[i] = (x[i] - self.mean[i]) / nonzero(self.stddev[i]); }
+  }
+
+  fn inverse_transform(&self, x: &mut [f64]) {
+    for i in 0..x.len() { x[i] = x[i] * nonzero(self.stddev[i]) + self.mean[i]; }
+  }
+}
+
+
+/// Centers each feature on its median, and scales it by its interquartile 
+/// range, given the feature in the training set. Less sensitive to outliers 
+/// than `StandardScaler`.
+///
+#[derive(Clone, Debug, RustcEncodable, RustcDecodable)]
+pub struct RobustScaler {
+  median: Vec<f64>,
+  iqr: Vec<f64>
+}
+
+impl Scaler for RobustScaler {
+  fn fit<'a, I>(rows: I) -> Self where I : Iterator<Item = &'a [f64]> {
+    let mut columns = columns(rows);
+
+    for c in columns.iter_mut() {
+      c.sort_by(|a, b| a.partial_cmp(b).unwrap_or(::std::cmp::Ordering::Equal));
+    }
+
+    RobustScaler {
+      median: columns.iter().map(|c| quantile(c, 0.5f64)).collect(),
+      iqr: columns
+        .iter()
+        .map(|c| quantile(c, 0.75f64) - quantile(c, 0.25f64))
+        .collect()
+    }
+  }
+
+  fn transform(&self, x: &mut [f64]) {
+    for i in 0..x.len() { x[i] = (x[i] - self.median[i]) / nonzero(self.iqr[i]); }
+  }
+
+  fn inverse_transform(&self, x: &mut [f64]) {
+    for i in 0..x.len() { x[i] = x[i] * nonzero(self.iqr[i]) + self.median[i]; }
+  }
+}
+
+
+/// Returns a quantile of sorted values, interpolating linearly between the 
+/// closest values.
+///
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+  if sorted.is_empty() { return 0f64 }
+
+  let pos = q * (sorted.len() - 1) as f64;
+  let lo = pos.floor() as usize;
+  let hi = pos.ceil() as usize;
+
+  sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64)
+}
+
+
+/// Returns a copy of a training set, with the inputs and expected results 
+/// transformed by scalers.
+///
+pub fn scale<T, I, O>(tset: &[T], inputs: &I, targets: &O) -> Vec<Example>
+  where T : TrainingSetMember,
+        I : Scaler,
+        O : Scaler
+{
+  tset
+    .iter()
+    .map(|m| {
+      let mut input = m.input().to_vec();
+      let mut expected = m.expected().to_vec();
+
+      inputs.transform(&mut input);
+      targets.transform(&mut expected);
+
+      Example::new(input, expected)
+    })
+    .collect()
+}
+
+
+/// A neural net with the scalers it was trained with, so predictions apply 
+/// exactly the same transformation. Inputs are transformed before they are 
+/// fed to the neural net, and the transformation of the expected results is 
+/// reverted on its outputs (which are available through `output`, once a 
+/// prediction is made). Serializes with the scalers.
+///
+/// The scaled net only implements `Predictor`, as its outputs aren't in the 
+/// same space as the nodes and weights of the neural net. To train it, train 
+/// the inner neural net (`nnet_mut`) on a scaled training set (see `scale`).
+///
+#[derive(Clone, Debug, RustcEncodable, RustcDecodable)]
+pub struct ScaledNet<N, I, O> {
+  nnet: N,
+  inputs: I,
+  targets: O,
+  input: Vec<f64>,
+  output: Vec<f64>
+}
+
+impl<N, I, O> ScaledNet<N, I, O> where I : Scaler, O : Scaler {
+  /// Wraps a neural net with fitted scalers.
+  ///
+  #[inline(always)]
+  pub fn new(nnet: N, inputs: I, targets: O) -> ScaledNet<N, I, O> {
+    ScaledNet { 
+      nnet: nnet, 
+      inputs: inputs, 
+      targets: targets, 
+      input: Vec::new(), 
+      output: Vec::new() 
+    }
+  }
+
+  /// Returns a copy of a training set, transformed by the scalers.
+  ///
+  #[inline]
+  pub fn scale<T>(&self, tset: &[T]) -> Vec<Example> where T : TrainingSetMember {
+    scale(tset, &self.inputs, &self.targets)
+  }
+
+  /// Returns the neural net.
+  ///
+  #[inline(always)] pub fn nnet(&self) -> &N { &self.nnet }
+
+  /// Returns a mutable reference to the neural net.
+  ///
+  #[inline(always)] pub fn nnet_mut(&mut self) -> &mut N { &mut self.nnet }
+
+  /// Returns the scaler of the inputs.
+  ///
+  #[inline(always)] pub fn inputs(&self) -> &I { &self.inputs }
+
+  /// Returns the scaler of the expected results.
+  ///
+  #[inline(always)] pub fn targets(&self) -> &O { &self.targets }
+
+  /// Consumes the scaled net, and returns the neural net and scalers.
+  ///
+  #[inline(always)] 
+  pub fn into_parts(self) -> (N, I, O) { (self.nnet, self.inputs, self.targets) }
+}
+
+impl<P, N, I, O> Predictor<P> for ScaledNet<N, I, O>
+  where P : NeuralNetParameters,
+        N : Predictor<P>,
+        I : Scaler,
+        O : Scaler
+{
+  #[inline(always)] fn dim_input() -> usize { N::dim_input() }
+
+  #[inline(always)] fn dim_output() -> usize { N::dim_output() }
+
+  fn predict(&mut self, inp: &[f64]) {
+    self.input.clear();
+    self.input.extend(inp.iter().cloned());
+    self.inputs.transform(&mut self.input);
+    self.nnet.predict(&self.input);
+
+    self.output.clear();
+    self.output.extend(self.nnet.output().iter().cloned());
+    self.targets.inverse_transform(&mut self.output);
+  }
+
+  #[inline(always)] fn output(&self) -> &[f64] { &self.output }
+}
@@ -0,0 +1,461 @@
+use std::cmp::Ordering;
+
+use num_cpus;
+use rand::{thread_rng, Rng};
+use rand::distributions::IndependentSample;
+use rand::distributions::range::Range;
+use scoped_threadpool::Pool;
+use prelude::*;
+use validation;
+
+
+/// Values a hyperparameter can take.
+///
+#[derive(Clone, Debug)]
+pub enum Domain {
+  /// A fixed set of values, which shouldn't be empty.
+  ///
+  Discrete(Vec<f64>),
+
+  /// Values uniformly distributed between a lower and upper bound.
+  ///
+  Uniform(f64, f64),
+
+  /// Values log-uniformly distributed between a lower and upper bound. Both 
+  /// bounds should be greater than 0.
+  ///
+  LogUniform(f64, f64)
+}
+
+impl Domain {
+  /// Returns `n` evenly spaced values of the domain. Discrete domains always 
+  /// return all of their values.
+  ///
+  fn grid(&self, n: usize) -> Vec<f64> {
+    match *self {
+      Domain::Discrete(ref vs) => vs.clone(),
+      _ if n <= 1 => vec![self.interpolate(0.5f64)],
+      _ => (0..n).map(|i| self.interpolate(i as f64 / (n - 1) as f64)).collect()
+    }
+  }
+
+  /// Returns a random value of the domain.
+  ///
+  fn sample<R>(&self, rng: &mut R) -> f64 where R : Rng {
+    match *self {
+      Domain::Discrete(ref vs) => vs[rng.gen_range(0, vs.len())],
+      _ => self.interpolate(Range::new(0f64, 1f64).ind_sample(rng))
+    }
+  }
+
+  /// Maps `t` in `[0, 1]` to a value between the bounds of a continuous 
+  /// domain.
+  ///
+  fn interpolate(&self, t: f64) -> f64 {
+    match *self {
+      Domain::Uniform(lb, ub) => lb + t * (ub - lb),
+      Domain::LogUniform(lb, ub) => (lb.ln() + t * (ub.ln() - lb.ln())).exp(),
+      Domain::Discrete(_) => unreachable!()
+    }
+  }
+}
+
+
+/// Named hyperparameters, and their domains.
+///
+#[derive(Clone, Debug)]
+pub struct SearchSpace {
+  params: Vec<(String, Domain)>
+}
+
+impl SearchSpace {
+  /// Creates an empty search space.
+  ///
+  #[inline(always)]
+  pub fn new() -> SearchSpace { SearchSpace { params: Vec::new() } }
+
+  /// Adds a hyperparameter to the search space.
+  ///
+  /// # Panics
+  ///
+  /// When the domain is discrete, and has no values.
+  ///
+  #[inline]
+  pub fn param(mut self, name: &str, domain: Domain) -> SearchSpace {
+    if let Domain::Discrete(ref vs) = domain {
+      if vs.is_empty() { panic!("discrete domain of {:?} should have values", name) }
+    }
+
+    self.params.push((name.to_string(), domain));
+    self
+  }
+
+  /// Returns every combination of values, taking `resolution` evenly spaced 
+  /// values from each continuous domain.
+  ///
+  pub fn grid(&self, resolution: usize) -> Vec<Candidate> {
+    let mut candidates = vec![Candidate { values: Vec::new() }];
+
+    for &(ref name, ref domain) in self.params.iter() {
+      let values = domain.grid(resolution);
+      let mut next = Vec::with_capacity(candidates.len() * values.len());
+
+      for candidate in candidates.iter() {
+        for &v in values.iter() {
+          let mut c = candidate.clone();
+          c.values.push((name.clone(), v));
+          next.push(c);
+        }
+      }
+
+      candidates = next;
+    }
+
+    candidates
+  }
+
+  /// Returns a candidate with a random value for each hyperparameter.
+  ///
+  pub fn sample<R>(&self, rng: &mut R) -> Candidate where R : Rng {
+    Candidate { 
+      values: self.params
+        .iter()
+        .map(|&(ref name, ref domain)| (name.clone(), domain.sample(rng)))
+        .collect()
+    }
+  }
+}
+
+
+/// A value for each hyperparameter in a search space.
+///
+#[derive(Clone, Debug)]
+pub struct Candidate {
+  values: Vec<(String, f64)>
+}
+
+impl Candidate {
+  /// Returns the value of a hyperparameter.
+  ///
+  /// # Panics
+  ///
+  /// When the hyperparameter isn't in the search space.
+  ///
+  pub fn get(&self, name: &str) -> f64 {
+    match self.values.iter().find(|&&(ref n, _)| n == name) {
+      Some(&(_, v)) => v,
+      None => panic!("unknown hyperparameter: {:?}", name)
+    }
+  }
+
+  /// Returns the value of every hyperparameter.
+  ///
+  #[inline(always)] pub fn values(&self) -> &[(String, f64)] { &self.values }
+}
+
+
+/// How candidates are scored.
+///
+#[derive(Copy, Clone, Debug)]
+pub enum Validation {
+  /// Trains on a random part of the training set, and scores on the 
+  /// remaining fraction.
+  ///
+  Holdout(f64),
+
+  /// Averages the score over k folds.
+  ///
+  KFold(usize),
+
+  /// Averages the score over k folds, where each fold has the same 
+  /// proportion of each class (see `validation::stratified_folds`). Single 
+  /// outputs are split into classes at the threshold (see 
+  /// `validation::threshold`).
+  ///
+  StratifiedKFold(usize, f64)
+}
+
+
+/// How candidates are chosen from a search space.
+///
+#[derive(Copy, Clone, Debug)]
+pub enum Strategy {
+  /// Every combination of values, taking `resolution` evenly spaced values 
+  /// from each continuous domain. Every candidate is trained with `budget`.
+  ///
+  Grid { 
+    #[allow(missing_docs)] resolution: usize, 
+    #[allow(missing_docs)] budget: usize 
+  },
+
+  /// Random candidates, each trained with `budget`.
+  ///
+  Random { 
+    #[allow(missing_docs)] candidates: usize, 
+    #[allow(missing_docs)] budget: usize 
+  },
+
+  /// Random candidates, trained with `min_budget`. The best `1 / eta` 
+  /// candidates are kept, and trained again with `eta` times the budget, 
+  /// until one candidate remains.
+  ///
+  SuccessiveHalving { 
+    #[allow(missing_docs)] candidates: usize, 
+    #[allow(missing_docs)] min_budget: usize, 
+    #[allow(missing_docs)] eta: usize 
+  },
+
+  /// Several rounds of successive halving, trading off the number of 
+  /// candidates and the budget each starts with, where no candidate is 
+  /// trained with more than `max_budget`.
+  ///
+  Hyperband { 
+    #[allow(missing_docs)] max_budget: usize, 
+    #[allow(missing_docs)] eta: usize 
+  }
+}
+
+
+/// Score of a candidate when trained with a budget.
+///
+#[derive(Clone, Debug)]
+pub struct Trial {
+  #[allow(missing_docs)] pub candidate: Candidate,
+  #[allow(missing_docs)] pub budget: usize,
+  #[allow(missing_docs)] pub score: f64
+}
+
+
+/// Searches for the best hyperparameters for a training set. Returns a 
+/// leaderboard with an entry for each candidate, ranked from best to worst. 
+/// Candidates trained with a larger budget are ranked ahead of those 
+/// eliminated early.
+///
+/// `evaluate` is called with a candidate, a budget (for example, a number of 
+/// epochs), the members to train with, and the members to score with. It 
+/// should train a new neural net, and return its score, where a lower score 
+/// is better (for example, the average error). Candidates are evaluated in 
+/// parallel on a thread pool. 
+///
+/// Learning rates and momentum constants can be set for each candidate using 
+/// `params::DynamicTrainerParams`. Since the number of hidden nodes is fixed 
+/// at compile time, searching over it requires matching the candidate's value 
+/// to a neural net type:
+///
+/// ```ignore
+/// search(&space, &tset, strategy, Validation::KFold(3), 
+///   |candidate, epochs, train, validation| {
+///     DynamicTrainerParams::set_lrate(candidate.get("lrate"));
+///
+///     match candidate.get("hidden") as usize {
+///       4 => train_and_score::<Net4<_>>(epochs, train, validation),
+///       _ => train_and_score::<Net8<_>>(epochs, train, validation)
+///     }
+///   });
+/// ```
+///
+/// # Panics
+///
+/// When the validation fraction isn't in the range `(0, 1)`, there are fewer 
+/// than 2 folds, or `eta` is less than 2.
+///
+pub fn search<T, F>(
+  space: &SearchSpace, 
+  tset: &[T], 
+  strategy: Strategy, 
+  validation: Validation, 
+  evaluate: F
+) -> Vec<Trial>
+  where T : TrainingSetMember + Sync,
+        F : Fn(&Candidate, usize, &[&T], &[&T]) -> f64 + Sync
+{
+  let mut rng = thread_rng();
+  let splits = splits(tset, validation);
+  let mut pool = Pool::new(num_cpus::get() as u32);
+  let mut board = match strategy {
+    Strategy::Grid { resolution, budget } => {
+      let candidates = space.grid(resolution);
+      score(&mut pool, tset, &splits, candidates, budget, &evaluate)
+    }
+    Strategy::Random { candidates, budget } => {
+      let candidates = (0..candidates).map(|_| space.sample(&mut rng)).collect();
+      score(&mut pool, tset, &splits, candidates, budget, &evaluate)
+    }
+    Strategy::SuccessiveHalving { candidates, min_budget, eta } => {
+      if eta < 2 { panic!("eta should be at least 2") }
+
+      let rounds = log(candidates, eta) + 1;
+      let candidates = (0..candidates).map(|_| space.sample(&mut rng)).collect();
+      halve(&mut pool, tset, &splits, candidates, min_budget, eta, rounds, 
+            &evaluate)
+    }
+    Strategy::Hyperband { max_budget, eta } => {
+      if eta < 2 { panic!("eta should be at least 2") }
+
+      let brackets = log(max_budget, eta);
+      let mut board = Vec::new();
+
+      for s in (0..brackets + 1).rev() {
+        let n = ((brackets + 1) as f64 / (s + 1) as f64 * 
+          eta.pow(s as u32) as f64).ceil() as usize;
+        let budget = max_budget / eta.pow(s as u32);
+        let candidates = (0..n).map(|_| space.sample(&mut rng)).collect();
+
+        board.extend(
+          halve(&mut pool, tset, &splits, candidates, budget, eta, s + 1, 
+                &evaluate));
+      }
+
+      board
+    }
+  };
+
+  rank(&mut board);
+  board
+}
+
+
+/// Indices of the members to train with, and to score with, for each fold.
+///
+type Splits = Vec<(Vec<usize>, Vec<usize>)>;
+
+
+/// Splits the training set into folds.
+///
+fn splits<T>(tset: &[T], validation: Validation) -> Splits 
+  where T : TrainingSetMember 
+{
+  let seed = thread_rng().gen();
+  let folds = match validation {
+    Validation::Holdout(frac) => {
+      if frac <= 0f64 || frac >= 1f64 { 
+        panic!("validation fraction should be in the range (0, 1)") 
+      }
+
+      let split = validation::Split::random(tset, frac, 0f64, seed);
+
+      return vec![(split.train_indices().to_vec(), split.validation_indices().to_vec())];
+    }
+    Validation::KFold(k) => validation::folds(tset, k, seed),
+    Validation::StratifiedKFold(k, threshold) => 
+      validation::stratified_folds(tset, k, threshold, seed)
+  };
+
+  (0..folds.len())
+    .map(|i| {
+      let train = folds
+        .iter()
+        .enumerate()
+        .filter(|&(j, _)| j != i)
+        .flat_map(|(_, fold)| fold.iter().cloned())
+        .collect();
+
+      (train, folds[i].clone())
+    })
+    .collect()
+}
+
+
+/// Evaluates each candidate on every fold in parallel, and returns a trial 
+/// for each candidate with its average score.
+///
+fn score<T, F>(
+  pool: &mut Pool,
+  tset: &[T],
+  splits: &Splits,
+  candidates: Vec<Candidate>,
+  budget: usize,
+  evaluate: &F
+) -> Vec<Trial>
+  where T : TrainingSetMember + Sync,
+        F : Fn(&Candidate, usize, &[&T], &[&T]) -> f64 + Sync
+{
+  let mut scores = vec![0f64; candidates.len() * splits.len()];
+
+  pool.scoped(|scope| {
+    for (i, score) in scores.iter_mut().enumerate() {
+      let candidate = &candidates[i / splits.len()];
+      let &(ref train, ref validation) = &splits[i % splits.len()];
+
+      scope.execute(move || {
+        let train: Vec<&T> = train.iter().map(|&j| &tset[j]).collect();
+        let validation: Vec<&T> = validation.iter().map(|&j| &tset[j]).collect();
+
+        *score = evaluate(candidate, budget, &train, &validation);
+      });
+    }
+  });
+
+  candidates
+    .into_iter()
+    .enumerate()
+    .map(|(i, candidate)| {
+      let folds = &scores[i * splits.len()..(i + 1) * splits.len()];
+      let score = folds.iter().fold(0f64, |acc, s| acc + s) / folds.len() as f64;
+
+      Trial { candidate: candidate, budget: budget, score: score }
+    })
+    .collect()
+}
+
+
+/// Runs `rounds` of successive halving on a set of candidates. Returns the 
+/// last trial of each candidate.
+///
+fn halve<T, F>(
+  pool: &mut Pool,
+  tset: &[T],
+  splits: &Splits,
+  candidates: Vec<Candidate>,
+  min_budget: usize,
+  eta: usize,
+  rounds: usize,
+  evaluate: &F
+) -> Vec<Trial>
+  where T : TrainingSetMember + Sync,
+        F : Fn(&Candidate, usize, &[&T], &[&T]) -> f64 + Sync
+{
+  let mut board = Vec::with_capacity(candidates.len());
+  let mut candidates = candidates;
+  let mut budget = ::std::cmp::max(min_budget, 1);
+
+  for round in 0..rounds {
+    let mut trials = score(pool, tset, splits, candidates, budget, evaluate);
+    rank(&mut trials);
+
+    let keep = if round + 1 < rounds { ::std::cmp::max(trials.len() / eta, 1) } 
+               else { 0 };
+
+    candidates = trials[..keep].iter().map(|t| t.candidate.clone()).collect();
+    board.extend(trials.into_iter().skip(keep));
+    budget *= eta;
+  }
+
+  board
+}
+
+
+/// Returns the number of times `n` can be divided by `eta` before it is less 
+/// than `eta`.
+///
+#[inline]
+fn log(n: usize, eta: usize) -> usize {
+  let mut n = n;
+  let mut i = 0;
+
+  while n >= eta { n /= eta; i += 1; }
+
+  i
+}
+
+
+/// Sorts trials from best to worst.
+///
+fn rank(trials: &mut Vec<Trial>) {
+  trials.sort_by(|a, b| {
+    match b.budget.cmp(&a.budget) {
+      Ordering::Equal => a.score.partial_cmp(&b.score).unwrap_or(Ordering::Equal),
+      ord => ord
+    }
+  });
+}
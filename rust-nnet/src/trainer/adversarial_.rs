@@ -0,0 +1,281 @@
+use std::marker::PhantomData;
+
+use gradient;
+use prelude::*;
+use rand::{thread_rng, Rng};
+use rand::distributions::IndependentSample;
+use rand::distributions::range::Range;
+
+
+/// Method used to generate adversarial examples. Each method perturbs the 
+/// input of a training set member to increase the loss of a neural net, 
+/// while keeping every feature within `epsilon` of the original input 
+/// (an L-infinity budget).
+///
+#[derive(Copy, Clone, Debug)]
+pub enum Attack {
+  /// Fast Gradient Sign Method. Takes a single step of size `epsilon` in the 
+  /// direction of the sign of the input gradient of the loss.
+  ///
+  Fgsm { 
+    #[allow(missing_docs)] epsilon: f64 
+  },
+
+  /// Projected Gradient Descent. Starts at a random point within the budget, 
+  /// and takes `iterations` steps of size `step` in the direction of the 
+  /// sign of the input gradient of the loss, projecting back into the 
+  /// budget after each step.
+  ///
+  Pgd { 
+    #[allow(missing_docs)] epsilon: f64, 
+    #[allow(missing_docs)] step: f64, 
+    #[allow(missing_docs)] iterations: usize 
+  }
+}
+
+impl Attack {
+  /// Generates an adversarial input for a training set member.
+  ///
+  pub fn perturb<X, Y, N, M>(&self, nn: &mut N, member: &M) -> Vec<f64>
+    where X : TrainerParameters,
+          Y : NeuralNetParameters,
+          N : NeuralNet<Y>,
+          M : TrainingSetMember
+  {
+    match *self {
+      Attack::Fgsm { epsilon } => fgsm::<X, Y, _, _>(nn, member, epsilon),
+      Attack::Pgd { epsilon, step, iterations } => 
+        pgd::<X, Y, _, _>(nn, member, epsilon, step, iterations)
+    }
+  }
+}
+
+
+/// Computes the gradient of the loss of a neural net with respect to an 
+/// input, given the expected result. The loss is the one minimized by the 
+/// trainer parameter's `ErrorGradient`.
+///
+pub fn loss_gradient<X, Y, N>(nn: &mut N, inp: &[f64], exp: &[f64]) -> Vec<f64>
+  where X : TrainerParameters,
+        Y : NeuralNetParameters,
+        N : NeuralNet<Y>
+{
+  nn.predict(inp);
+
+  // `erroutput` points in the direction that reduces the loss, so it is 
+  // negated to get the gradient.
+  let deltas: Vec<f64> = (0..N::dim_output())
+    .map(|i| {
+      -X::ErrorGradient::erroutput::<Y::ActivationFunction>(
+        exp[i], 
+        nn.node(Node::Output(i)))
+    })
+    .collect();
+
+  gradient::backpropagate(nn, &deltas)
+}
+
+
+/// Generates an adversarial input for a training set member using the Fast 
+/// Gradient Sign Method.
+///
+pub fn fgsm<X, Y, N, M>(nn: &mut N, member: &M, epsilon: f64) -> Vec<f64>
+  where X : TrainerParameters,
+        Y : NeuralNetParameters,
+        N : NeuralNet<Y>,
+        M : TrainingSetMember
+{
+  let inp = member.input();
+
+  loss_gradient::<X, Y, _>(nn, inp, member.expected())
+    .iter()
+    .zip(inp.iter())
+    .map(|(g, x)| x + epsilon * sign(*g))
+    .collect()
+}
+
+
+/// Generates an adversarial input for a training set member using Projected 
+/// Gradient Descent.
+///
+pub fn pgd<X, Y, N, M>(
+  nn: &mut N, 
+  member: &M, 
+  epsilon: f64, 
+  step: f64, 
+  iterations: usize
+) -> Vec<f64>
+  where X : TrainerParameters,
+        Y : NeuralNetParameters,
+        N : NeuralNet<Y>,
+        M : TrainingSetMember
+{
+  let inp = member.input();
+  let exp = member.expected();
+  let mut rng = thread_rng();
+  let mut adv: Vec<f64> = if epsilon > 0f64 {
+    let range = Range::new(-epsilon, epsilon);
+    inp.iter().map(|x| x + range.ind_sample(&mut rng)).collect()
+  } else {
+    inp.to_vec()
+  };
+
+  for _ in 0..iterations {
+    let grad = loss_gradient::<X, Y, _>(nn, &adv, exp);
+
+    for i in 0..adv.len() {
+      let x = adv[i] + step * sign(grad[i]);
+      adv[i] = x.max(inp[i] - epsilon).min(inp[i] + epsilon);
+    }
+  }
+
+  adv
+}
+
+
+/// Generates an adversarial example for each member of a training set. The 
+/// expected result of each example is the same as the original member's.
+///
+pub fn augment<X, Y, N, T>(
+  nn: &mut N, 
+  tset: &[T], 
+  attack: Attack
+) -> Vec<(Vec<f64>, Vec<f64>)>
+  where X : TrainerParameters,
+        Y : NeuralNetParameters,
+        N : NeuralNet<Y>,
+        T : TrainingSetMember
+{
+  tset
+    .iter()
+    .map(|member| {
+      (attack.perturb::<X, Y, _, _>(nn, member), member.expected().to_vec())
+    })
+    .collect()
+}
+
+
+#[inline(always)]
+fn sign(x: f64) -> f64 {
+  if x > 0f64 { 1f64 } else if x < 0f64 { -1f64 } else { 0f64 }
+}
+
+
+/// Wraps a trainer to train a neural net on a mix of clean and adversarial 
+/// examples. The stopping condition is based on a max number of epochs.
+///
+/// At the start of each epoch, adversarial examples are generated against 
+/// the current weights for a random `ratio` of the training set, and mixed 
+/// (in random order) with a copy of the clean training set. The mixed set is 
+/// handed to `train_epoch`, which should run a single epoch of any trainer. 
+/// For example:
+///
+/// ```ignore
+/// AdversarialTrainer::<_, _, _, MyTrainerParams, _>::with_epochs(
+///   &mut nn, &tset, Attack::Fgsm { epsilon: 0.1 }, 0.5, 100, 
+///   |nn, mixed| { 
+///     SeqEpochTrainer::<_, _, MyTrainerParams, _>
+///       ::with_epochs(nn, mixed, 1)
+///       .train(); 
+///   });
+/// ```
+///
+pub struct AdversarialTrainer<'a, N : 'a, T : 'a, F, X, Y> {
+  nnet: &'a mut N,
+  tset: &'a [T],
+  attack: Attack,
+  ratio: f64,
+  train_epoch: F,
+  epoch: usize,
+  max_epochs: usize,
+  tptype: PhantomData<X>,
+  nptype: PhantomData<Y>
+}
+
+impl<'a, N, T, F, X, Y> AdversarialTrainer<'a, N, T, F, X, Y>
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        F : FnMut(&mut N, &[(Vec<f64>, Vec<f64>)]),
+        X : TrainerParameters, 
+        Y : NeuralNetParameters
+{
+  /// Creates a new trainer for a neural net, given a training set, where the 
+  /// stopping condition is the number of epochs.
+  ///
+  /// # Panics
+  ///
+  /// When `ratio` is not in the range `[0, 1]`.
+  ///
+  #[inline(always)]
+  pub fn with_epochs(
+    nnet: &'a mut N, 
+    tset: &'a [T], 
+    attack: Attack, 
+    ratio: f64,
+    epochs: usize,
+    train_epoch: F
+  ) -> Self {
+    if ratio < 0f64 || ratio > 1f64 { 
+      panic!("ratio should be in the range [0, 1]") 
+    }
+
+    AdversarialTrainer {
+      nnet: nnet,
+      tset: tset,
+      attack: attack,
+      ratio: ratio,
+      train_epoch: train_epoch,
+      epoch: 0,
+      max_epochs: epochs,
+      tptype: PhantomData,
+      nptype: PhantomData
+    }
+  }
+}
+
+impl<'a, N, T, F, X, Y> NeuralNetTrainer for AdversarialTrainer<'a, N, T, F, X, Y>
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        F : FnMut(&mut N, &[(Vec<f64>, Vec<f64>)]),
+        X : TrainerParameters, 
+        Y : NeuralNetParameters
+{ }
+
+impl<'a, N, T, F, X, Y> Iterator for AdversarialTrainer<'a, N, T, F, X, Y>
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        F : FnMut(&mut N, &[(Vec<f64>, Vec<f64>)]),
+        X : TrainerParameters, 
+        Y : NeuralNetParameters
+{
+  type Item = usize;
+
+  fn next(&mut self) -> Option<usize> {
+    if self.epoch == self.max_epochs {
+      None
+    } else {
+      let epoch = self.epoch;
+      let mut rng = thread_rng();
+      let mut mixed = Vec::with_capacity(2 * self.tset.len());
+
+      for member in self.tset.iter() {
+        let exp = member.expected().to_vec();
+
+        if rng.gen::<f64>() < self.ratio {
+          let adv = self.attack.perturb::<X, Y, _, _>(self.nnet, member);
+          mixed.push((adv, exp.clone()));
+        }
+
+        mixed.push((member.input().to_vec(), exp));
+      }
+
+      rng.shuffle(&mut mixed);
+
+      (self.train_epoch)(self.nnet, &mixed);
+
+      self.epoch += 1;
+
+      Some(epoch)
+    }
+  }
+}
@@ -0,0 +1,153 @@
+use std::marker::PhantomData;
+
+use params::MSEFunction;
+use prelude::*;
+use rand::{thread_rng, Rng};
+use rand::distributions::IndependentSample;
+use rand::distributions::normal::Normal;
+use trainer::fitness_::ErrorFitness;
+use trainer::util;
+
+
+/// Simulated annealing trainer, where the stopping condition is the number 
+/// of epochs. Each epoch proposes a number of moves at the temperature given 
+/// by the cooling schedule. Yields the epoch, and the best fitness found so 
+/// far.
+///
+/// A move perturbs the weights of a copy of the neural net, and is accepted 
+/// with the Metropolis criterion: always if the fitness doesn't get worse, 
+/// and otherwise with probability `exp(delta / temperature)`. The search 
+/// starts at the weights of the neural net, so it can be run after another 
+/// trainer has converged. The weights with the best fitness found so far 
+/// are copied into the trained neural net.
+///
+pub struct AnnealingTrainer<'a, N : 'a, F, C, X, Y> {
+  nnet: &'a mut N,
+  current: N,
+  fitness: F,
+  schedule: C,
+  weights: Vec<f64>,
+  score: f64,
+  best: f64,
+  epoch: usize,
+  max_epochs: usize,
+  tptype: PhantomData<X>,
+  nptype: PhantomData<Y>
+}
+
+impl<'a, N, T, C, X, Y> AnnealingTrainer<'a, N, ErrorFitness<'a, T, MSEFunction, Y>, C, X, Y>
+  where N : NeuralNet<Y> + Clone,
+        T : TrainingSetMember,
+        C : CoolingSchedule,
+        X : AnnealingParameters,
+        Y : NeuralNetParameters
+{
+  /// Creates a new trainer for a neural net, given a training set and a 
+  /// cooling schedule, where the stopping condition is the number of epochs. 
+  /// The fitness is the negative mean squared error over the training set.
+  ///
+  #[inline(always)]
+  pub fn with_epochs(nnet: &'a mut N, tset: &'a [T], schedule: C, epochs: usize) -> Self {
+    Self::with_fitness(nnet, ErrorFitness::new(tset), schedule, epochs)
+  }
+}
+
+impl<'a, N, F, C, X, Y> AnnealingTrainer<'a, N, F, C, X, Y>
+  where N : NeuralNet<Y> + Clone,
+        F : Fitness<N>,
+        C : CoolingSchedule,
+        X : AnnealingParameters,
+        Y : NeuralNetParameters
+{
+  /// Creates a new trainer for a neural net, given a fitness function and a 
+  /// cooling schedule, where the stopping condition is the number of epochs.
+  ///
+  pub fn with_fitness(nnet: &'a mut N, fitness: F, schedule: C, epochs: usize) -> Self {
+    let mut current = nnet.clone();
+    let score = fitness.fitness(&mut current);
+
+    AnnealingTrainer {
+      weights: util::weights::<Y, _>(nnet),
+      nnet: nnet,
+      current: current,
+      fitness: fitness,
+      schedule: schedule,
+      score: score,
+      best: score,
+      epoch: 0,
+      max_epochs: epochs,
+      tptype: PhantomData,
+      nptype: PhantomData
+    }
+  }
+
+  /// Returns the temperature of the current epoch.
+  ///
+  #[inline]
+  pub fn temperature(&self) -> f64 {
+    self.schedule.temperature(X::initial_temperature(), self.epoch)
+  }
+}
+
+impl<'a, N, F, C, X, Y> NeuralNetTrainer for AnnealingTrainer<'a, N, F, C, X, Y>
+  where N : NeuralNet<Y> + Clone,
+        F : Fitness<N>,
+        C : CoolingSchedule,
+        X : AnnealingParameters,
+        Y : NeuralNetParameters
+{ }
+
+impl<'a, N, F, C, X, Y> Iterator for AnnealingTrainer<'a, N, F, C, X, Y>
+  where N : NeuralNet<Y> + Clone,
+        F : Fitness<N>,
+        C : CoolingSchedule,
+        X : AnnealingParameters,
+        Y : NeuralNetParameters
+{
+  type Item = (usize, f64);
+
+  fn next(&mut self) -> Option<(usize, f64)> {
+    if self.epoch == self.max_epochs {
+      return None
+    }
+
+    let epoch = self.epoch;
+    let temperature = self.temperature();
+    let mut rng = thread_rng();
+    let normal = Normal::new(0f64, X::step_size());
+    let mut proposal = self.weights.clone();
+
+    for _ in 0..X::moves() {
+      proposal.clone_from(&self.weights);
+
+      let forced = rng.gen_range(0, proposal.len());
+
+      for (k, w) in proposal.iter_mut().enumerate() {
+        if k == forced || rng.gen::<f64>() < X::perturbation_rate() {
+          *w += normal.ind_sample(&mut rng);
+        }
+      }
+
+      util::set_weights(&mut self.current, &proposal);
+
+      let score = self.fitness.fitness(&mut self.current);
+      let delta = score - self.score;
+      let accept = delta >= 0f64 || 
+        (temperature > 0f64 && rng.gen::<f64>() < (delta / temperature).exp());
+
+      if accept {
+        self.score = score;
+        ::std::mem::swap(&mut self.weights, &mut proposal);
+
+        if score > self.best {
+          self.best = score;
+          util::set_weights(self.nnet, &self.weights);
+        }
+      }
+    }
+
+    self.epoch += 1;
+
+    Some((epoch, self.best))
+  }
+}
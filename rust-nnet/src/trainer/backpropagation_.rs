@@ -86,6 +86,84 @@ impl<'a, N, T, X, Y>  Iterator for SeqEpochTrainer<'a, N, T, X, Y>
 }
 
 
+/// Back-propagation trainer where the stopping criteria is bounded by the 
+/// epoch. Weights are updated for each example in the training set, after 
+/// noise (see `DenoisingParameters`) is applied to a copy of the example.
+///
+pub struct DenoisingTrainer<'a, N : 'a, T : 'a, X, Y> {
+  nnet: &'a mut N,
+  tset: &'a [T],
+  state: TrainerState,
+  epoch: usize,
+  max_epochs: usize,
+  tptype: PhantomData<X>,
+  nptype: PhantomData<Y>
+}
+
+impl<'a, N, T, X, Y> DenoisingTrainer<'a, N, T, X, Y> 
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        X : DenoisingParameters, 
+        Y : NeuralNetParameters
+{
+  /// Creates a new trainer for a neural net, given a training set, where the 
+  /// max number of epochs is set to `::std::usize::MAX`.
+  #[inline(always)]
+  pub fn new(nnet: &'a mut N, tset: &'a [T]) -> Self {
+    Self::with_epochs(nnet, tset, ::std::usize::MAX)
+  }
+
+  /// Creates a new trainer for a neural net, given a training set, where the 
+  /// stopping condition is the number of epochs.
+  ///
+  #[inline(always)]
+  pub fn with_epochs(nnet: &'a mut N, tset: &'a [T], epochs: usize) -> Self {
+    DenoisingTrainer {
+      nnet: nnet,
+      tset: tset,
+      state: TrainerState::new::<_, N>(),
+      epoch: 0,
+      max_epochs: epochs,
+      tptype: PhantomData,
+      nptype: PhantomData
+    }
+  }
+}
+
+impl<'a, N, T, X, Y> NeuralNetTrainer for DenoisingTrainer<'a, N, T, X, Y> 
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        X : DenoisingParameters, 
+        Y : NeuralNetParameters
+{ }
+
+impl<'a, N, T, X, Y>  Iterator for DenoisingTrainer<'a, N, T, X, Y> 
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        X : DenoisingParameters, 
+        Y : NeuralNetParameters
+{
+  type Item = usize;
+
+  fn next(&mut self) -> Option<usize> {
+    if self.epoch == self.max_epochs {
+      None
+    } else {
+      let epoch = self.epoch;
+
+      for member in self.tset.iter() {
+        util::update_state_noisy::<X, Y, _, _>(self.nnet, &mut self.state, member);
+        util::update_weights(self.nnet, &self.state);
+      }
+
+      self.epoch += 1;
+
+      Some(epoch)
+    }
+  }
+}
+
+
 /// Back-propagation trainer where the stopping condition is primarily the 
 /// calculated average error, with an optional stopping condition based on the 
 /// epoch. Weights are updated for each example in the training set.
@@ -394,4 +472,293 @@ impl<'a, N, T, X, Y> Iterator for BatchEpochTrainerParallel<'a, N, T, X, Y>
       Some(epoch)
     }
   }
-}
\ No newline at end of file
+}
+
+
+/// Back-propagation trainer over a re-iterable training set, where the 
+/// stopping criteria is bounded by the epoch. `source` is called at the start 
+/// of each epoch to produce the members of the training set, so the 
+/// training set never needs to be held in memory. Weights are updated for 
+/// each example in the training set.
+///
+pub struct SeqEpochTrainerStream<'a, N : 'a, S, X, Y> {
+  nnet: &'a mut N,
+  source: S,
+  state: TrainerState,
+  epoch: usize,
+  max_epochs: usize,
+  tptype: PhantomData<X>,
+  nptype: PhantomData<Y>
+}
+
+impl<'a, N, S, I, X, Y> SeqEpochTrainerStream<'a, N, S, X, Y> 
+  where N : NeuralNet<Y>, 
+        S : FnMut() -> I,
+        I : Iterator,
+        I::Item : TrainingSetMember,
+        X : TrainerParameters, 
+        Y : NeuralNetParameters
+{
+  /// Creates a new trainer for a neural net, given a source for the training 
+  /// set, where the max number of epochs is set to `::std::usize::MAX`.
+  ///
+  #[inline(always)]
+  pub fn new(nnet: &'a mut N, source: S) -> Self {
+    Self::with_epochs(nnet, source, ::std::usize::MAX)
+  }
+
+  /// Creates a new trainer for a neural net, given a source for the training 
+  /// set, where the stopping condition is the number of epochs.
+  ///
+  #[inline(always)]
+  pub fn with_epochs(nnet: &'a mut N, source: S, epochs: usize) -> Self {
+    SeqEpochTrainerStream {
+      nnet: nnet,
+      source: source,
+      state: TrainerState::new::<_, N>(),
+      epoch: 0,
+      max_epochs: epochs,
+      tptype: PhantomData,
+      nptype: PhantomData
+    }
+  }
+}
+
+impl<'a, N, S, I, X, Y> NeuralNetTrainer for SeqEpochTrainerStream<'a, N, S, X, Y> 
+  where N : NeuralNet<Y>, 
+        S : FnMut() -> I,
+        I : Iterator,
+        I::Item : TrainingSetMember,
+        X : TrainerParameters, 
+        Y : NeuralNetParameters
+{ }
+
+impl<'a, N, S, I, X, Y> Iterator for SeqEpochTrainerStream<'a, N, S, X, Y> 
+  where N : NeuralNet<Y>, 
+        S : FnMut() -> I,
+        I : Iterator,
+        I::Item : TrainingSetMember,
+        X : TrainerParameters, 
+        Y : NeuralNetParameters
+{
+  type Item = usize;
+
+  fn next(&mut self) -> Option<usize> {
+    if self.epoch == self.max_epochs {
+      None
+    } else {
+      let epoch = self.epoch;
+
+      for member in (self.source)() {
+        util::update_state::<X, Y, _, _>(self.nnet, &mut self.state, &member);
+        util::update_weights(self.nnet, &self.state);
+      }
+
+      self.epoch += 1;
+
+      Some(epoch)
+    }
+  }
+}
+
+
+/// Back-propagation trainer over a re-iterable training set, where the 
+/// stopping condition is primarily the calculated average error, with an 
+/// optional stopping condition based on the epoch. `source` is called at the 
+/// start of each epoch to produce the members of the training set. Weights 
+/// are updated for each example in the training set.
+///
+pub struct SeqErrorAverageTrainerStream<'a, N : 'a, S, X, Y> {
+  nnet: &'a mut N,
+  source: S,
+  epoch: usize,
+  state: TrainerState,
+  err_target: f64,
+  max_epochs: usize,
+  tptype: PhantomData<X>,
+  nptype: PhantomData<Y>
+}
+
+impl<'a, N, S, I, X, Y> SeqErrorAverageTrainerStream<'a, N, S, X, Y>
+  where N : NeuralNet<Y>, 
+        S : FnMut() -> I,
+        I : Iterator,
+        I::Item : TrainingSetMember,
+        X : TrainerParametersWithErrorFunction, 
+        Y : NeuralNetParameters
+{
+  /// Creates a new trainer for a neural net, given a source for the training 
+  /// set and target `err`. By default, the max number of epochs the trainer 
+  /// can run is the max value for `usize`.
+  ///
+  /// # Panics
+  ///
+  /// When `err` is less than or equal to 0.
+  ///
+  #[inline(always)] 
+  pub fn new(nnet: &'a mut N, source: S, err: f64) -> Self {
+    Self::with_epoch_bound(nnet, source, err, ::std::usize::MAX)
+  }
+
+  /// Creates a new trainer for a neural net, given a source for the training 
+  /// set and target `err` and target max epoch as an alternate stopping 
+  /// condition.
+  ///
+  /// # Panics
+  /// 
+  /// When `err` is less than or equal to 0.
+  ///
+  #[inline(always)] 
+  pub fn with_epoch_bound(
+    nnet: &'a mut N, 
+    source: S, 
+    err: f64, 
+    max: usize
+  ) -> Self { 
+    if err <= 0f64 { panic!("target err should be greater than 0") }
+
+    SeqErrorAverageTrainerStream {
+      nnet: nnet,
+      source: source,
+      epoch: 0,
+      state: TrainerState::new::<_, N>(),
+      err_target: err,
+      max_epochs: max,
+      tptype: PhantomData,
+      nptype: PhantomData
+    } 
+  }
+}
+
+impl<'a, N, S, I, X, Y> NeuralNetTrainer 
+  for SeqErrorAverageTrainerStream<'a, N, S, X, Y>
+  where N : NeuralNet<Y>, 
+        S : FnMut() -> I,
+        I : Iterator,
+        I::Item : TrainingSetMember,
+        X : TrainerParametersWithErrorFunction, 
+        Y : NeuralNetParameters
+{ }
+
+impl<'a, N, S, I, X, Y> Iterator for SeqErrorAverageTrainerStream<'a, N, S, X, Y>
+  where N : NeuralNet<Y>, 
+        S : FnMut() -> I,
+        I : Iterator,
+        I::Item : TrainingSetMember,
+        X : TrainerParametersWithErrorFunction, 
+        Y : NeuralNetParameters
+{
+  type Item = (usize, f64);
+
+  fn next(&mut self) -> Option<(usize, f64)> {
+    if self.epoch == self.max_epochs {
+      None
+    } else {
+      let mut err = 0f64;
+      let mut len = 0usize;
+
+      for member in (self.source)() {
+        util::update_state::<X, Y, _, _>(self.nnet, &mut self.state, &member);
+        util::update_weights(self.nnet, &self.state);
+        
+        let exp = member.expected();
+        let act = self.nnet.layer(Layer::Output);
+
+        err += X::ErrorFunction::error(act.iter(), exp.iter());
+        len += 1;
+      }
+
+      // An empty epoch has nothing left to learn, so it is treated as 
+      // having reached the target error.
+      let avg = if len == 0 { 0f64 } else { err / len as f64 };
+      let ret = Some((self.epoch, avg));
+
+      if avg <= self.err_target { 
+        self.max_epochs = self.epoch;
+      } else {
+        self.epoch += 1;
+      }
+
+      ret
+    }
+  }
+}
+
+
+/// Back-propagation trainer over a re-iterable training set, where the 
+/// stopping condition is based on a max number of epochs. `source` is called 
+/// at the start of each epoch to produce the members of the training set. 
+/// Weights are updated at the end of each epoch.
+///
+pub struct BatchEpochTrainerStream<'a, N : 'a, S, X, Y> {
+  nnet: &'a mut N,
+  source: S,
+  state: TrainerState,
+  epoch: usize,
+  max_epochs: usize,
+  tptype: PhantomData<X>,
+  nptype: PhantomData<Y>
+}
+
+impl<'a, N, S, I, X, Y> BatchEpochTrainerStream<'a, N, S, X, Y> 
+  where N : NeuralNet<Y>, 
+        S : FnMut() -> I,
+        I : Iterator,
+        I::Item : TrainingSetMember,
+        X : TrainerParameters, 
+        Y : NeuralNetParameters
+{
+  /// Creates a new trainer for a neural net, given a source for the training 
+  /// set, where the stopping condition is the number of epochs.
+  ///
+  #[inline(always)]
+  pub fn new(nnet: &'a mut N, source: S, epochs: usize) -> Self {
+    BatchEpochTrainerStream {
+      nnet: nnet,
+      source: source,
+      state: TrainerState::new::<_, N>(),
+      epoch: 0,
+      max_epochs: epochs,
+      tptype: PhantomData,
+      nptype: PhantomData
+    }
+  }
+}
+
+impl<'a, N, S, I, X, Y> NeuralNetTrainer for BatchEpochTrainerStream<'a, N, S, X, Y> 
+  where N : NeuralNet<Y>, 
+        S : FnMut() -> I,
+        I : Iterator,
+        I::Item : TrainingSetMember,
+        X : TrainerParameters, 
+        Y : NeuralNetParameters
+{ }
+
+impl<'a, N, S, I, X, Y> Iterator for BatchEpochTrainerStream<'a, N, S, X, Y> 
+  where N : NeuralNet<Y>, 
+        S : FnMut() -> I,
+        I : Iterator,
+        I::Item : TrainingSetMember,
+        X : TrainerParameters, 
+        Y : NeuralNetParameters
+{
+  type Item = usize;
+
+  fn next(&mut self) -> Option<usize> {
+    if self.epoch == self.max_epochs {
+      None
+    } else {
+      let epoch = self.epoch;
+
+      for member in (self.source)() {
+        util::update_state::<X, Y, _, _>(self.nnet, &mut self.state, &member);
+      }
+
+      util::update_weights(self.nnet, &self.state);
+
+      self.epoch += 1;
+
+      Some(epoch)
+    }
+  }
+}
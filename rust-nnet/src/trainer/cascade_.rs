@@ -0,0 +1,363 @@
+use std::marker::PhantomData;
+
+use prelude::*;
+use rand::thread_rng;
+use rand::distributions::IndependentSample;
+use rand::distributions::range::Range;
+use trainer::linalg::dot;
+use trainer::rprop_::{update, IRpropMinus};
+
+
+/// A neural net built by Cascade-Correlation. Each hidden unit receives 
+/// the inputs, the bias, and the output of every hidden unit added before 
+/// it. Each output unit receives the inputs, the bias, and the output of 
+/// every hidden unit.
+///
+/// The hidden units don't form a single layer, so the network only 
+/// implements `Predictor`, and not `NeuralNet`. The activation of each 
+/// hidden unit is available through `hidden`.
+///
+#[derive(Clone, RustcEncodable, RustcDecodable)]
+pub struct CascadeNetwork<P, D> {
+  values: Vec<f64>,
+  output: Vec<f64>,
+  hidden: Vec<Vec<f64>>,
+  woutput: Vec<Vec<f64>>,
+  ptype: PhantomData<P>,
+  dtype: PhantomData<D>
+}
+
+impl<P, D> CascadeNetwork<P, D> 
+  where P : NeuralNetParameters,
+        D : Dimensions
+{
+  /// Creates a network without hidden units, and with random output 
+  /// weights.
+  ///
+  pub fn new() -> CascadeNetwork<P, D> {
+    let mut values = vec![0f64; D::inputs() + 1];
+
+    values[D::inputs()] = P::BiasWeightFunction::biasw();
+
+    CascadeNetwork {
+      values: values,
+      output: vec![0f64; D::outputs()],
+      hidden: Vec::new(),
+      woutput: (0..D::outputs())
+        .map(|_| {
+          (0..D::inputs() + 1)
+            .map(|_| P::WeightFunction::initw(D::inputs(), D::outputs()))
+            .collect()
+        })
+        .collect(),
+      ptype: PhantomData,
+      dtype: PhantomData
+    }
+  }
+
+  /// Returns the number of hidden units.
+  ///
+  #[inline(always)] pub fn hidden_units(&self) -> usize { self.hidden.len() }
+
+  /// Returns the activation of each hidden unit, from the last prediction.
+  ///
+  #[inline(always)] pub fn hidden(&self) -> &[f64] { &self.values[D::inputs() + 1..] }
+
+  /// Adds a hidden unit with the given input weights. The output weights 
+  /// from the new unit start at 0.
+  ///
+  fn push(&mut self, weights: Vec<f64>) {
+    self.hidden.push(weights);
+    self.values.push(0f64);
+
+    for ws in self.woutput.iter_mut() { ws.push(0f64); }
+  }
+}
+
+impl<P, D> Predictor<P> for CascadeNetwork<P, D> 
+  where P : NeuralNetParameters,
+        D : Dimensions
+{
+  #[inline(always)] fn dim_input() -> usize { D::inputs() }
+
+  #[inline(always)] fn dim_output() -> usize { D::outputs() }
+
+  fn predict(&mut self, inp: &[f64]) {
+    assert!(inp.len() == D::inputs());
+
+    for i in 0..D::inputs() { self.values[i] = inp[i]; }
+
+    for k in 0..self.hidden.len() {
+      let s = D::inputs() + 1 + k;
+      let sum = dot(&self.hidden[k], &self.values[..s]);
+
+      self.values[s] = P::ActivationFunction::activation(sum);
+    }
+
+    for o in 0..D::outputs() {
+      self.output[o] = P::ActivationFunction::activation(dot(&self.woutput[o], &self.values));
+    }
+  }
+
+  #[inline(always)] fn output(&self) -> &[f64] { &self.output }
+}
+
+
+/// Default step sizes for the iRprop- updates of the output weights and 
+/// candidate units.
+///
+struct StepSizes;
+
+impl RpropParameters for StepSizes { }
+
+
+/// iRprop- update of a set of weights.
+///
+struct Rprop {
+  steps: Vec<f64>,
+  changes: Vec<f64>,
+  pgradient: Vec<f64>
+}
+
+impl Rprop {
+  fn new(len: usize) -> Rprop {
+    Rprop { 
+      steps: vec![StepSizes::initial_step(); len], 
+      changes: vec![0f64; len],
+      pgradient: vec![0f64; len] 
+    }
+  }
+
+  /// Takes a step against the gradient.
+  ///
+  fn step(&mut self, ws: &mut [f64], gradient: &[f64]) {
+    for k in 0..ws.len() {
+      self.pgradient[k] = update::<IRpropMinus, StepSizes>(
+        &mut ws[k], 
+        &mut self.steps[k], 
+        &mut self.changes[k], 
+        gradient[k], 
+        self.pgradient[k], 
+        false);
+    }
+  }
+}
+
+
+/// Cascade-Correlation trainer, that grows a network one hidden unit at a 
+/// time. Yields the number of hidden units, and the mean squared error over 
+/// the training set after the output weights were trained. Stops once the 
+/// error reaches `target_error`, or `max_hidden` units were added.
+///
+/// Before each unit is added, a pool of candidate units (connected to the 
+/// inputs and every existing hidden unit) is trained to maximize the 
+/// correlation between its output and the residual error of the network. 
+/// The best candidate is added, its input weights are frozen, and the 
+/// output weights are retrained. Training also stops if no candidate has a 
+/// defined correlation.
+///
+pub struct CascadeTrainer<'a, T : 'a, X, Y, D> {
+  nnet: &'a mut CascadeNetwork<Y, D>,
+  tset: &'a [T],
+  activations: Vec<Vec<f64>>,
+  done: bool,
+  tptype: PhantomData<X>
+}
+
+impl<'a, T, X, Y, D> CascadeTrainer<'a, T, X, Y, D>
+  where T : TrainingSetMember,
+        X : CascadeParameters,
+        Y : NeuralNetParameters,
+        D : Dimensions
+{
+  /// Creates a new trainer for a network, given a training set. Hidden units 
+  /// are added to any the network already has.
+  ///
+  /// # Panics
+  ///
+  /// When there are no candidate units.
+  ///
+  pub fn new(nnet: &'a mut CascadeNetwork<Y, D>, tset: &'a [T]) -> Self {
+    if X::candidates() == 0 { panic!("there should be at least 1 candidate unit") }
+
+    let activations = tset
+      .iter()
+      .map(|member| {
+        nnet.predict(member.input());
+        nnet.values.clone()
+      })
+      .collect();
+
+    CascadeTrainer {
+      nnet: nnet,
+      tset: tset,
+      activations: activations,
+      done: false,
+      tptype: PhantomData
+    }
+  }
+
+  /// Trains the output weights on the cached activations. Returns the 
+  /// residual error (actual - expected) of each output, for each member, 
+  /// and the mean squared error.
+  ///
+  fn train_outputs(&mut self) -> (Vec<Vec<f64>>, f64) {
+    let len = self.activations[0].len();
+    let mut rprops: Vec<Rprop> = (0..D::outputs()).map(|_| Rprop::new(len)).collect();
+    let mut gradient = vec![0f64; len];
+
+    for epoch in 0..X::output_epochs() + 1 {
+      let errors = self.residuals();
+
+      if epoch == X::output_epochs() { 
+        let mse = errors.iter().fold(0f64, |acc, es| {
+          es.iter().fold(acc, |acc, e| acc + e * e)
+        }) / (self.tset.len() * D::outputs()) as f64;
+
+        return (errors, mse)
+      }
+
+      for o in 0..D::outputs() {
+        for g in gradient.iter_mut() { *g = 0f64; }
+
+        for (p, vs) in self.activations.iter().enumerate() {
+          let act = Y::ActivationFunction::activation(dot(&self.nnet.woutput[o], vs));
+          let delta = errors[p][o] * Y::ActivationFunction::derivative_from_output(act);
+
+          for i in 0..len { gradient[i] += delta * vs[i]; }
+        }
+
+        rprops[o].step(&mut self.nnet.woutput[o], &gradient);
+      }
+    }
+
+    unreachable!()
+  }
+
+  /// Returns the error (actual - expected) of each output, for each member.
+  ///
+  fn residuals(&self) -> Vec<Vec<f64>> {
+    self.activations
+      .iter()
+      .zip(self.tset.iter())
+      .map(|(vs, member)| {
+        (0..D::outputs())
+          .map(|o| {
+            Y::ActivationFunction::activation(dot(&self.nnet.woutput[o], vs)) - 
+              member.expected()[o]
+          })
+          .collect()
+      })
+      .collect()
+  }
+
+  /// Trains a candidate unit to maximize the correlation between its output 
+  /// and the residual errors. Returns its weights, and the correlation.
+  ///
+  fn train_candidate(&self, errors: &[Vec<f64>]) -> (Vec<f64>, f64) {
+    let len = self.activations[0].len();
+    let n = self.activations.len() as f64;
+    let range = Range::new(-1f64, 1f64);
+    let mut rng = thread_rng();
+    let mut ws: Vec<f64> = (0..len).map(|_| range.ind_sample(&mut rng)).collect();
+    let mut rprop = Rprop::new(len);
+    let mut gradient = vec![0f64; len];
+    let mean: Vec<f64> = (0..D::outputs())
+      .map(|o| errors.iter().fold(0f64, |acc, es| acc + es[o]) / n)
+      .collect();
+
+    for epoch in 0..X::candidate_epochs() + 1 {
+      let values: Vec<f64> = self.activations
+        .iter()
+        .map(|vs| Y::ActivationFunction::activation(dot(&ws, vs)))
+        .collect();
+      let vmean = values.iter().fold(0f64, |acc, v| acc + v) / n;
+      let covariances: Vec<f64> = (0..D::outputs())
+        .map(|o| {
+          values
+            .iter()
+            .zip(errors.iter())
+            .fold(0f64, |acc, (v, es)| acc + (v - vmean) * (es[o] - mean[o]))
+        })
+        .collect();
+
+      if epoch == X::candidate_epochs() {
+        return (ws, covariances.iter().fold(0f64, |acc, c| acc + c.abs()))
+      }
+
+      for g in gradient.iter_mut() { *g = 0f64; }
+
+      for (p, vs) in self.activations.iter().enumerate() {
+        let delta = (0..D::outputs()).fold(0f64, |acc, o| {
+          acc + covariances[o].signum() * (errors[p][o] - mean[o])
+        }) * Y::ActivationFunction::derivative_from_output(values[p]);
+
+        // Gradient of the negative correlation, so the step ascends.
+        for i in 0..len { gradient[i] -= delta * vs[i]; }
+      }
+
+      rprop.step(&mut ws, &gradient);
+    }
+
+    unreachable!()
+  }
+}
+
+impl<'a, T, X, Y, D> NeuralNetTrainer for CascadeTrainer<'a, T, X, Y, D>
+  where T : TrainingSetMember,
+        X : CascadeParameters,
+        Y : NeuralNetParameters,
+        D : Dimensions
+{ }
+
+impl<'a, T, X, Y, D> Iterator for CascadeTrainer<'a, T, X, Y, D>
+  where T : TrainingSetMember,
+        X : CascadeParameters,
+        Y : NeuralNetParameters,
+        D : Dimensions
+{
+  type Item = (usize, f64);
+
+  fn next(&mut self) -> Option<(usize, f64)> {
+    if self.done || self.tset.is_empty() {
+      return None
+    }
+
+    let (errors, mse) = self.train_outputs();
+    let units = self.nnet.hidden.len();
+
+    if mse <= X::target_error() || units >= X::max_hidden() {
+      self.done = true;
+      return Some((units, mse))
+    }
+
+    // Add the candidate most correlated with the residual error, and cache 
+    // its output for each member. Candidates whose correlation is undefined 
+    // are ignored.
+    let best = (0..X::candidates())
+      .map(|_| self.train_candidate(&errors))
+      .filter(|c| !c.1.is_nan())
+      .fold(None, |best: Option<(Vec<f64>, f64)>, c| {
+        match best {
+          Some(b) => Some(if c.1 > b.1 { c } else { b }),
+          None => Some(c)
+        }
+      });
+    let ws = match best {
+      Some((ws, _)) => ws,
+      None => {
+        self.done = true;
+        return Some((units, mse))
+      }
+    };
+
+    for vs in self.activations.iter_mut() {
+      let v = Y::ActivationFunction::activation(dot(&ws, vs));
+      vs.push(v);
+    }
+
+    self.nnet.push(ws);
+
+    Some((units, mse))
+  }
+}
@@ -0,0 +1,349 @@
+use std::marker::PhantomData;
+
+use num_cpus;
+use params::MSEFunction;
+use prelude::*;
+use rand::{thread_rng, Rng};
+use rand::distributions::IndependentSample;
+use rand::distributions::normal::Normal;
+use scoped_threadpool::Pool;
+use trainer::fitness_::ErrorFitness;
+use trainer::linalg;
+use trainer::linalg::dot;
+use trainer::util;
+
+
+/// State of a single run of CMA-ES, between restarts.
+///
+struct Strategy {
+  lambda: usize,
+  weights: Vec<f64>,
+  mueff: f64,
+  cc: f64,
+  cs: f64,
+  c1: f64,
+  cmu: f64,
+  damps: f64,
+  chin: f64,
+  mean: Vec<f64>,
+  sigma: f64,
+  c: Vec<Vec<f64>>,
+  b: Vec<Vec<f64>>,
+  d: Vec<f64>,
+  pc: Vec<f64>,
+  ps: Vec<f64>,
+  generation: usize,
+  decomposed: usize,
+  history: Vec<f64>
+}
+
+impl Strategy {
+  fn new(mean: Vec<f64>, sigma: f64, lambda: usize) -> Strategy {
+    let n = mean.len() as f64;
+    let mu = lambda / 2;
+    let raw: Vec<f64> = (0..mu)
+      .map(|i| (mu as f64 + 0.5f64).ln() - ((i + 1) as f64).ln())
+      .collect();
+    let sum = raw.iter().fold(0f64, |acc, w| acc + w);
+    let weights: Vec<f64> = raw.iter().map(|w| w / sum).collect();
+    let mueff = 1f64 / weights.iter().fold(0f64, |acc, w| acc + w * w);
+    let c1 = 2f64 / ((n + 1.3f64).powi(2) + mueff);
+    let cs = (mueff + 2f64) / (n + mueff + 5f64);
+
+    Strategy {
+      lambda: lambda,
+      weights: weights,
+      mueff: mueff,
+      cc: (4f64 + mueff / n) / (n + 4f64 + 2f64 * mueff / n),
+      cs: cs,
+      c1: c1,
+      cmu: (1f64 - c1).min(
+        2f64 * (mueff - 2f64 + 1f64 / mueff) / ((n + 2f64).powi(2) + mueff)),
+      damps: 1f64 + 2f64 * (((mueff - 1f64) / (n + 1f64)).sqrt() - 1f64).max(0f64) + cs,
+      chin: n.sqrt() * (1f64 - 1f64 / (4f64 * n) + 1f64 / (21f64 * n * n)),
+      c: identity(mean.len()),
+      b: identity(mean.len()),
+      d: vec![1f64; mean.len()],
+      pc: vec![0f64; mean.len()],
+      ps: vec![0f64; mean.len()],
+      mean: mean,
+      sigma: sigma,
+      generation: 0,
+      decomposed: 0,
+      history: Vec::new()
+    }
+  }
+
+  /// Samples an offspring from the search distribution.
+  ///
+  fn sample<R>(&self, normal: &Normal, rng: &mut R) -> Vec<f64> where R : Rng {
+    let dz: Vec<f64> = self.d.iter().map(|d| d * normal.ind_sample(rng)).collect();
+
+    (0..self.mean.len())
+      .map(|i| self.mean[i] + self.sigma * dot(&self.b[i], &dz))
+      .collect()
+  }
+
+  /// Moves the mean towards the fittest offspring, and adapts the step size 
+  /// and covariance matrix. `ranked` is the offspring ordered by fitness, 
+  /// best first.
+  ///
+  fn update(&mut self, xs: &[Vec<f64>], ranked: &[usize]) {
+    let n = self.mean.len();
+    let old = self.mean.clone();
+
+    for i in 0..n {
+      self.mean[i] = self.weights
+        .iter()
+        .enumerate()
+        .fold(0f64, |acc, (k, w)| acc + w * xs[ranked[k]][i]);
+    }
+
+    let step: Vec<f64> = (0..n).map(|i| (self.mean[i] - old[i]) / self.sigma).collect();
+
+    // C^(-1/2) * step = B * D^-1 * B^T * step
+    let scaled: Vec<f64> = (0..n)
+      .map(|j| (0..n).fold(0f64, |acc, i| acc + self.b[i][j] * step[i]) / self.d[j])
+      .collect();
+    let cs = (self.cs * (2f64 - self.cs) * self.mueff).sqrt();
+
+    for i in 0..n {
+      self.ps[i] = (1f64 - self.cs) * self.ps[i] + cs * dot(&self.b[i], &scaled);
+    }
+
+    let norm = dot(&self.ps, &self.ps).sqrt();
+    let hsig = norm / 
+      (1f64 - (1f64 - self.cs).powi(2 * (self.generation as i32 + 1))).sqrt() / 
+      self.chin < 1.4f64 + 2f64 / (n as f64 + 1f64);
+    let cc = if hsig { (self.cc * (2f64 - self.cc) * self.mueff).sqrt() } else { 0f64 };
+
+    for i in 0..n {
+      self.pc[i] = (1f64 - self.cc) * self.pc[i] + cc * step[i];
+    }
+
+    let ys: Vec<Vec<f64>> = (0..self.weights.len())
+      .map(|k| (0..n).map(|i| (xs[ranked[k]][i] - old[i]) / self.sigma).collect())
+      .collect();
+    let correction = if hsig { 0f64 } else { self.cc * (2f64 - self.cc) };
+
+    for i in 0..n {
+      for j in 0..i + 1 {
+        let rank_mu = self.weights
+          .iter()
+          .zip(ys.iter())
+          .fold(0f64, |acc, (w, y)| acc + w * y[i] * y[j]);
+        let c = (1f64 - self.c1 - self.cmu) * self.c[i][j] + 
+          self.c1 * (self.pc[i] * self.pc[j] + correction * self.c[i][j]) + 
+          self.cmu * rank_mu;
+
+        self.c[i][j] = c;
+        self.c[j][i] = c;
+      }
+    }
+
+    self.sigma *= ((self.cs / self.damps) * (norm / self.chin - 1f64)).exp();
+    self.generation += 1;
+
+    // The eigendecomposition is only updated once every few generations, 
+    // when the number of evaluations since the last update exceeds the lag.
+    let lag = self.lambda as f64 / (self.c1 + self.cmu) / n as f64 / 10f64;
+
+    if ((self.generation - self.decomposed) * self.lambda) as f64 > lag {
+      let (values, vectors) = linalg::symmetric_eigen(&self.c);
+
+      self.d = values.iter().map(|v| v.max(1e-20f64).sqrt()).collect();
+      self.b = vectors;
+      self.decomposed = self.generation;
+    }
+  }
+
+  /// Returns whether the strategy has stopped making progress.
+  ///
+  fn converged(&self, tolerance: f64) -> bool {
+    let dmax = self.d.iter().fold(0f64, |acc, &d| acc.max(d));
+    let dmin = self.d.iter().fold(::std::f64::INFINITY, |acc, &d| acc.min(d));
+    let window = 10 + (30f64 * self.mean.len() as f64 / self.lambda as f64).ceil() as usize;
+    let flat = self.history.len() >= window && {
+      let recent = &self.history[self.history.len() - window..];
+      let hi = recent.iter().fold(::std::f64::NEG_INFINITY, |acc, &f| acc.max(f));
+      let lo = recent.iter().fold(::std::f64::INFINITY, |acc, &f| acc.min(f));
+      hi - lo < tolerance
+    };
+
+    !self.sigma.is_finite() || self.sigma * dmax < tolerance || dmax > 1e7f64 * dmin || flat
+  }
+}
+
+
+#[inline]
+fn identity(n: usize) -> Vec<Vec<f64>> {
+  (0..n).map(|i| (0..n).map(|j| if i == j { 1f64 } else { 0f64 }).collect()).collect()
+}
+
+
+/// Covariance matrix adaptation evolution strategy (CMA-ES) trainer, where 
+/// the stopping condition is the number of generations (epochs). Yields the 
+/// best fitness found so far after each generation.
+///
+/// When a run stops making progress, the strategy is restarted from a random 
+/// point with a larger population (IPOP-CMA-ES). The fitness of each 
+/// generation is computed in parallel. The weights of the fittest offspring 
+/// found so far are copied into the trained neural net.
+///
+pub struct CmaEsTrainer<'a, N : 'a, F, X, Y> {
+  nnet: &'a mut N,
+  fitness: F,
+  nets: Vec<N>,
+  strategy: Strategy,
+  restarts: usize,
+  best: f64,
+  pool: Pool,
+  epoch: usize,
+  max_epochs: usize,
+  tptype: PhantomData<X>,
+  nptype: PhantomData<Y>
+}
+
+impl<'a, N, T, X, Y> CmaEsTrainer<'a, N, ErrorFitness<'a, T, MSEFunction, Y>, X, Y>
+  where N : NeuralNet<Y> + Clone,
+        T : TrainingSetMember,
+        X : CmaParameters,
+        Y : NeuralNetParameters
+{
+  /// Creates a new trainer for a neural net, given a training set, where the 
+  /// max number of epochs is set to `::std::usize::MAX`. The fitness is the 
+  /// negative mean squared error over the training set.
+  ///
+  #[inline(always)]
+  pub fn new(nnet: &'a mut N, tset: &'a [T]) -> Self {
+    Self::with_epochs(nnet, tset, ::std::usize::MAX)
+  }
+
+  /// Creates a new trainer for a neural net, given a training set, where the 
+  /// stopping condition is the number of epochs. The fitness is the negative 
+  /// mean squared error over the training set.
+  ///
+  #[inline(always)]
+  pub fn with_epochs(nnet: &'a mut N, tset: &'a [T], epochs: usize) -> Self {
+    Self::with_fitness(nnet, ErrorFitness::new(tset), epochs)
+  }
+}
+
+impl<'a, N, F, X, Y> CmaEsTrainer<'a, N, F, X, Y>
+  where N : NeuralNet<Y> + Clone,
+        F : Fitness<N>,
+        X : CmaParameters,
+        Y : NeuralNetParameters
+{
+  /// Creates a new trainer for a neural net, given a fitness function, where 
+  /// the stopping condition is the number of epochs. The search starts at 
+  /// the weights of the neural net.
+  ///
+  /// # Panics
+  ///
+  /// When the population has less than 2 offspring.
+  ///
+  pub fn with_fitness(nnet: &'a mut N, fitness: F, epochs: usize) -> Self {
+    let lambda = X::population(util::weight_count::<Y, N>());
+
+    if lambda < 2 {
+      panic!("population should have at least 2 offspring")
+    }
+
+    CmaEsTrainer {
+      nets: (0..lambda).map(|_| nnet.clone()).collect(),
+      strategy: Strategy::new(util::weights::<Y, _>(nnet), X::sigma(), lambda),
+      nnet: nnet,
+      fitness: fitness,
+      restarts: 0,
+      best: ::std::f64::NEG_INFINITY,
+      pool: Pool::new(num_cpus::get() as u32),
+      epoch: 0,
+      max_epochs: epochs,
+      tptype: PhantomData,
+      nptype: PhantomData
+    }
+  }
+
+  /// Returns the number of restarts so far.
+  ///
+  #[inline(always)] pub fn restarts(&self) -> usize { self.restarts }
+
+  /// Returns the current step size.
+  ///
+  #[inline(always)] pub fn sigma(&self) -> f64 { self.strategy.sigma }
+
+  /// Restarts the strategy from random weights, with a larger population.
+  ///
+  fn restart(&mut self) {
+    let lambda = self.strategy.lambda * X::population_growth();
+    let mean = (0..util::weight_count::<Y, N>())
+      .map(|_| Y::WeightFunction::initw(N::dim_input(), N::dim_output()))
+      .collect();
+
+    while self.nets.len() < lambda { 
+      let nn = self.nnet.clone();
+      self.nets.push(nn); 
+    }
+
+    self.nets.truncate(lambda);
+    self.strategy = Strategy::new(mean, X::sigma(), lambda);
+    self.restarts += 1;
+  }
+}
+
+impl<'a, N, F, X, Y> NeuralNetTrainer for CmaEsTrainer<'a, N, F, X, Y>
+  where N : NeuralNet<Y> + Clone + Send,
+        F : Fitness<N> + Sync,
+        X : CmaParameters,
+        Y : NeuralNetParameters
+{ }
+
+impl<'a, N, F, X, Y> Iterator for CmaEsTrainer<'a, N, F, X, Y>
+  where N : NeuralNet<Y> + Clone + Send,
+        F : Fitness<N> + Sync,
+        X : CmaParameters,
+        Y : NeuralNetParameters
+{
+  type Item = (usize, f64);
+
+  fn next(&mut self) -> Option<(usize, f64)> {
+    if self.epoch == self.max_epochs {
+      return None
+    }
+
+    let epoch = self.epoch;
+    let mut rng = thread_rng();
+    let normal = Normal::new(0f64, 1f64);
+    let xs: Vec<Vec<f64>> = (0..self.strategy.lambda)
+      .map(|_| self.strategy.sample(&normal, &mut rng))
+      .collect();
+
+    for (nn, x) in self.nets.iter_mut().zip(xs.iter()) {
+      util::set_weights(nn, x);
+    }
+
+    let scores = util::evaluate(&mut self.pool, &self.fitness, &mut self.nets);
+    let mut ranked: Vec<usize> = (0..scores.len()).collect();
+
+    ranked.sort_by(|&a, &b| {
+      scores[b].partial_cmp(&scores[a]).unwrap_or(::std::cmp::Ordering::Equal)
+    });
+
+    if scores[ranked[0]] > self.best {
+      self.best = scores[ranked[0]];
+      util::set_weights(self.nnet, &xs[ranked[0]]);
+    }
+
+    self.strategy.history.push(scores[ranked[0]]);
+    self.strategy.update(&xs, &ranked);
+
+    if self.restarts < X::restarts() && self.strategy.converged(X::tolerance()) {
+      self.restart();
+    }
+
+    self.epoch += 1;
+
+    Some((epoch, self.best))
+  }
+}
@@ -0,0 +1,198 @@
+use std::marker::PhantomData;
+
+use prelude::*;
+use trainer::linalg::dot;
+use trainer::util;
+use trainer::util::TrainerState;
+
+
+/// Scaled conjugate gradient (Møller) trainer where the stopping condition is 
+/// based on a max number of epochs. Yields the epoch, and the mean squared 
+/// error over the training set after the weights were updated.
+///
+/// Each epoch takes one step along a conjugate search direction of the 
+/// flattened weights, using the gradient over the whole training set. The 
+/// step length comes from a finite difference approximation of the second 
+/// order information along the direction, scaled like a trust region, so no 
+/// line search is needed. Training stops early once the gradient vanishes.
+///
+pub struct ScaledConjugateGradientTrainer<'a, N : 'a, T : 'a, X, Y> {
+  nnet: &'a mut N,
+  tset: &'a [T],
+  state: TrainerState,
+  weights: Vec<f64>,
+  residual: Vec<f64>,
+  direction: Vec<f64>,
+  error: f64,
+  delta: f64,
+  lambda: f64,
+  lambda_bar: f64,
+  success: bool,
+  steps: usize,
+  epoch: usize,
+  max_epochs: usize,
+  tptype: PhantomData<X>,
+  nptype: PhantomData<Y>
+}
+
+impl<'a, N, T, X, Y> ScaledConjugateGradientTrainer<'a, N, T, X, Y> 
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        X : ConjugateGradientParameters, 
+        Y : NeuralNetParameters
+{
+  /// Creates a new trainer for a neural net, given a training set, where the 
+  /// max number of epochs is set to `::std::usize::MAX`.
+  ///
+  #[inline(always)]
+  pub fn new(nnet: &'a mut N, tset: &'a [T]) -> Self {
+    Self::with_epochs(nnet, tset, ::std::usize::MAX)
+  }
+
+  /// Creates a new trainer for a neural net, given a training set, where the 
+  /// stopping condition is the number of epochs.
+  ///
+  pub fn with_epochs(nnet: &'a mut N, tset: &'a [T], epochs: usize) -> Self {
+    let mut state = TrainerState::new::<_, N>();
+    let error = util::batch_gradient(nnet, &mut state, tset);
+    let residual: Vec<f64> = state.gradient().iter().map(|g| -g).collect();
+
+    ScaledConjugateGradientTrainer {
+      weights: util::weights::<Y, _>(nnet),
+      nnet: nnet,
+      tset: tset,
+      state: state,
+      direction: residual.clone(),
+      residual: residual,
+      error: error,
+      delta: 0f64,
+      lambda: X::lambda(),
+      lambda_bar: 0f64,
+      success: true,
+      steps: 0,
+      epoch: 0,
+      max_epochs: epochs,
+      tptype: PhantomData,
+      nptype: PhantomData
+    }
+  }
+
+  /// Returns half the summed squared error at some weights, and leaves its 
+  /// gradient in the state.
+  ///
+  fn evaluate(&mut self, ws: &[f64]) -> f64 {
+    util::set_weights(self.nnet, ws);
+    util::batch_gradient(self.nnet, &mut self.state, self.tset)
+  }
+
+  /// Returns the mean squared error, given half the summed squared error.
+  ///
+  #[inline]
+  fn mse(&self, error: f64) -> f64 {
+    2f64 * error / (self.tset.len() * N::dim_output()) as f64
+  }
+}
+
+impl<'a, N, T, X, Y> NeuralNetTrainer for ScaledConjugateGradientTrainer<'a, N, T, X, Y> 
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        X : ConjugateGradientParameters, 
+        Y : NeuralNetParameters
+{ }
+
+impl<'a, N, T, X, Y> Iterator for ScaledConjugateGradientTrainer<'a, N, T, X, Y> 
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        X : ConjugateGradientParameters, 
+        Y : NeuralNetParameters
+{
+  type Item = (usize, f64);
+
+  fn next(&mut self) -> Option<(usize, f64)> {
+    if self.epoch == self.max_epochs {
+      return None
+    }
+
+    let epoch = self.epoch;
+    let pp = dot(&self.direction, &self.direction);
+
+    self.epoch += 1;
+
+    if pp == 0f64 {
+      self.max_epochs = self.epoch;
+      return Some((epoch, self.mse(self.error)))
+    }
+
+    // Approximate the second order information along the direction.
+    if self.success {
+      let sigma = X::sigma() / pp.sqrt();
+      let probe: Vec<f64> = self.weights
+        .iter()
+        .zip(self.direction.iter())
+        .map(|(w, p)| w + sigma * p)
+        .collect();
+
+      self.evaluate(&probe);
+      self.delta = (0..probe.len()).fold(0f64, |acc, i| {
+        acc + self.direction[i] * (self.state.gradient()[i] + self.residual[i])
+      }) / sigma;
+    }
+
+    // Scale, and make the approximation positive definite. The scaled 
+    // approximation is kept, so it's scaled further when the step fails.
+    self.delta += (self.lambda - self.lambda_bar) * pp;
+
+    if self.delta <= 0f64 {
+      self.lambda_bar = 2f64 * (self.lambda - self.delta / pp);
+      self.delta = -self.delta + self.lambda * pp;
+      self.lambda = self.lambda_bar;
+    }
+
+    let delta = self.delta;
+
+    let mu = dot(&self.direction, &self.residual);
+    let alpha = mu / delta;
+    let next: Vec<f64> = self.weights
+      .iter()
+      .zip(self.direction.iter())
+      .map(|(w, p)| w + alpha * p)
+      .collect();
+    let error = self.evaluate(&next);
+    let comparison = 2f64 * delta * (self.error - error) / (mu * mu);
+
+    if comparison >= 0f64 {
+      let residual: Vec<f64> = self.state.gradient().iter().map(|g| -g).collect();
+
+      self.steps += 1;
+
+      if self.steps % next.len() == 0 {
+        self.direction.clone_from(&residual);
+      } else {
+        let beta = (dot(&residual, &residual) - dot(&residual, &self.residual)) / mu;
+
+        for i in 0..residual.len() {
+          self.direction[i] = residual[i] + beta * self.direction[i];
+        }
+      }
+
+      if comparison >= 0.75f64 { self.lambda /= 4f64; }
+
+      self.weights = next;
+      self.residual = residual;
+      self.error = error;
+      self.lambda_bar = 0f64;
+      self.success = true;
+    } else {
+      util::set_weights(self.nnet, &self.weights);
+
+      self.lambda_bar = self.lambda;
+      self.success = false;
+    }
+
+    if comparison < 0.25f64 {
+      self.lambda += delta * (1f64 - comparison) / pp;
+    }
+
+    Some((epoch, self.mse(self.error)))
+  }
+}
@@ -0,0 +1,141 @@
+use std::marker::PhantomData;
+
+use prelude::*;
+use trainer::util;
+use trainer::util::TrainerState;
+
+
+/// Back-propagation trainer that trains a (student) neural net to mimic the 
+/// outputs of another (teacher) neural net. The stopping condition is based 
+/// on a max number of epochs. Weights are updated for each example in the 
+/// training set.
+///
+/// The teacher's outputs are softened with a temperature, by dividing the 
+/// weighted sum of each output node by the temperature, and activating it 
+/// with the student's activation function. The target for the student is a 
+/// blend of the expected result of a training set member (weighted by 
+/// `alpha`) and the softened output of the teacher (weighted by 
+/// `1 - alpha`).
+///
+pub struct DistillationTrainer<'a, N : 'a, M : 'a, T : 'a, X, Y, Z> {
+  student: &'a mut N,
+  teacher: &'a mut M,
+  tset: &'a [T],
+  state: TrainerState,
+  target: Vec<f64>,
+  temperature: f64,
+  alpha: f64,
+  epoch: usize,
+  max_epochs: usize,
+  tptype: PhantomData<X>,
+  nptype: PhantomData<Y>,
+  teacher_nptype: PhantomData<Z>
+}
+
+impl<'a, N, M, T, X, Y, Z> DistillationTrainer<'a, N, M, T, X, Y, Z>
+  where N : NeuralNet<Y>, 
+        M : NeuralNet<Z>,
+        T : TrainingSetMember, 
+        X : TrainerParameters, 
+        Y : NeuralNetParameters,
+        Z : NeuralNetParameters,
+        Z::ActivationFunction : InverseActivationFunction
+{
+  /// Creates a new trainer for a student neural net, given a teacher neural 
+  /// net and a training set, where the stopping condition is the number of 
+  /// epochs.
+  ///
+  /// # Panics
+  ///
+  /// When `temperature` is less than or equal to 0, or when `alpha` is not in 
+  /// the range `[0, 1]`.
+  ///
+  #[inline(always)]
+  pub fn with_epochs(
+    student: &'a mut N, 
+    teacher: &'a mut M, 
+    tset: &'a [T], 
+    temperature: f64,
+    alpha: f64,
+    epochs: usize
+  ) -> Self {
+    if temperature <= 0f64 { panic!("temperature should be greater than 0") }
+    if alpha < 0f64 || alpha > 1f64 { 
+      panic!("alpha should be in the range [0, 1]") 
+    }
+    if N::dim_output() != M::dim_output() {
+      panic!("student and teacher should have the same number of outputs")
+    }
+
+    DistillationTrainer {
+      student: student,
+      teacher: teacher,
+      tset: tset,
+      state: TrainerState::new::<_, N>(),
+      target: vec![0f64; N::dim_output()],
+      temperature: temperature,
+      alpha: alpha,
+      epoch: 0,
+      max_epochs: epochs,
+      tptype: PhantomData,
+      nptype: PhantomData,
+      teacher_nptype: PhantomData
+    }
+  }
+}
+
+impl<'a, N, M, T, X, Y, Z> NeuralNetTrainer 
+  for DistillationTrainer<'a, N, M, T, X, Y, Z>
+  where N : NeuralNet<Y>, 
+        M : NeuralNet<Z>,
+        T : TrainingSetMember, 
+        X : TrainerParameters, 
+        Y : NeuralNetParameters,
+        Z : NeuralNetParameters,
+        Z::ActivationFunction : InverseActivationFunction
+{ }
+
+impl<'a, N, M, T, X, Y, Z> Iterator for DistillationTrainer<'a, N, M, T, X, Y, Z>
+  where N : NeuralNet<Y>, 
+        M : NeuralNet<Z>,
+        T : TrainingSetMember, 
+        X : TrainerParameters, 
+        Y : NeuralNetParameters,
+        Z : NeuralNetParameters,
+        Z::ActivationFunction : InverseActivationFunction
+{
+  type Item = usize;
+
+  fn next(&mut self) -> Option<usize> {
+    if self.epoch == self.max_epochs {
+      None
+    } else {
+      let epoch = self.epoch;
+
+      for member in self.tset.iter() {
+        self.teacher.predict(member.input());
+
+        {
+          let exp = member.expected();
+          let soft = self.teacher.layer(Layer::Output);
+
+          for i in 0..N::dim_output() {
+            let sum = Z::ActivationFunction::inverse(soft[i]) / self.temperature;
+
+            self.target[i] = self.alpha * exp[i] + (1f64 - self.alpha) * 
+              Y::ActivationFunction::activation(sum);
+          }
+        }
+
+        let blended = (member.input(), &self.target[..]);
+
+        util::update_state::<X, Y, _, _>(self.student, &mut self.state, &blended);
+        util::update_weights(self.student, &self.state);
+      }
+
+      self.epoch += 1;
+
+      Some(epoch)
+    }
+  }
+}
@@ -0,0 +1,287 @@
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+
+use environment::Environment;
+use prelude::*;
+use rand::{thread_rng, Rng};
+use trainer::util;
+use trainer::util::TrainerState;
+use validation::argmax;
+
+
+/// A transition from one observation of an environment to the next.
+///
+#[derive(Clone, Debug)]
+pub struct Transition {
+  #[allow(missing_docs)] pub observation: Vec<f64>,
+  #[allow(missing_docs)] pub action: usize,
+  #[allow(missing_docs)] pub reward: f64,
+  #[allow(missing_docs)] pub next: Vec<f64>,
+  #[allow(missing_docs)] pub done: bool
+}
+
+
+/// A Q-learning agent (DQN), that uses a neural net to approximate the value 
+/// of each action given an observation. The neural net should have an output 
+/// node for each action. 
+///
+/// Actions are chosen with an epsilon-greedy policy. Transitions are stored 
+/// for experience replay, and the neural net is trained with backpropagation 
+/// on random batches of stored transitions. The targets for training are 
+/// computed with a copy of the neural net (the target network), which is 
+/// synced periodically.
+///
+/// Since the output nodes use a bounded activation function, rewards should 
+/// be scaled so that the discounted return stays within the range of the 
+/// activation function.
+///
+pub struct DqnAgent<N, X, Y> {
+  nnet: N,
+  target: N,
+  state: TrainerState,
+  replay: VecDeque<Transition>,
+  qvalues: Vec<f64>,
+  epsilon: f64,
+  replayed: usize,
+  tptype: PhantomData<X>,
+  nptype: PhantomData<Y>
+}
+
+impl<N, X, Y> DqnAgent<N, X, Y>
+  where N : NeuralNet<Y> + Clone, 
+        X : TrainerParameters + QLearningParameters, 
+        Y : NeuralNetParameters
+{
+  /// Creates a new agent that takes ownership of a neural net.
+  ///
+  pub fn new(nnet: N) -> Self {
+    DqnAgent {
+      target: nnet.clone(),
+      nnet: nnet,
+      state: TrainerState::new::<_, N>(),
+      replay: VecDeque::with_capacity(X::replay_capacity()),
+      qvalues: vec![0f64; N::dim_output()],
+      epsilon: X::epsilon_start(),
+      replayed: 0,
+      tptype: PhantomData,
+      nptype: PhantomData
+    }
+  }
+
+  /// Chooses an action for an observation with the epsilon-greedy policy.
+  ///
+  pub fn act(&mut self, obs: &[f64]) -> usize {
+    let mut rng = thread_rng();
+
+    if rng.gen::<f64>() < self.epsilon {
+      rng.gen_range(0, N::dim_output())
+    } else {
+      self.greedy(obs)
+    }
+  }
+
+  /// Chooses the action with the highest value for an observation.
+  ///
+  pub fn greedy(&mut self, obs: &[f64]) -> usize {
+    self.nnet.predict(obs);
+    argmax(self.nnet.layer(Layer::Output))
+  }
+
+  /// Stores a transition for experience replay. The oldest transition is 
+  /// dropped when the replay memory is full. Without experience replay, 
+  /// only the latest transition is kept.
+  ///
+  pub fn remember(&mut self, transition: Transition) {
+    while self.replay.len() >= X::replay_capacity().max(1) { 
+      self.replay.pop_front(); 
+    }
+
+    self.replay.push_back(transition);
+  }
+
+  /// Trains the neural net on a random batch of stored transitions. Does 
+  /// nothing until enough transitions are stored. Without experience 
+  /// replay, trains on the latest transition instead.
+  ///
+  pub fn replay(&mut self) {
+    if X::replay_capacity() == 0 {
+      if let Some(t) = self.replay.pop_back() { self.learn(&t); }
+
+      return
+    }
+
+    if self.replay.len() < X::batch_size() { return }
+
+    let mut rng = thread_rng();
+    let replay = ::std::mem::replace(&mut self.replay, VecDeque::new());
+
+    for _ in 0..X::batch_size() {
+      self.learn(&replay[rng.gen_range(0, replay.len())]);
+    }
+
+    self.replay = replay;
+  }
+
+  /// Trains the neural net on a single transition, and syncs the target 
+  /// network when it's due.
+  ///
+  fn learn(&mut self, t: &Transition) {
+    self.nnet.predict(&t.observation);
+
+    for (q, &act) in self.qvalues
+      .iter_mut()
+      .zip(self.nnet.layer(Layer::Output).iter()) 
+    {
+      *q = act;
+    }
+
+    self.qvalues[t.action] = if t.done {
+      t.reward
+    } else {
+      self.target.predict(&t.next);
+
+      let next = self.target
+        .layer(Layer::Output)
+        .iter()
+        .fold(::std::f64::NEG_INFINITY, |acc, &q| acc.max(q));
+
+      t.reward + X::discount() * next
+    };
+
+    {
+      let member = (&t.observation[..], &self.qvalues[..]);
+
+      util::update_state::<X, Y, _, _>(&mut self.nnet, &mut self.state, &member);
+    }
+    util::update_weights(&mut self.nnet, &self.state);
+
+    self.replayed += 1;
+
+    if X::sync_interval() == 0 || self.replayed % X::sync_interval() == 0 { 
+      self.target = self.nnet.clone(); 
+    }
+  }
+
+  /// Runs an episode in an environment, for at most `max_steps` steps. After 
+  /// each step, the transition is stored, and a batch is replayed. Returns 
+  /// the total reward for the episode.
+  ///
+  pub fn episode<E>(&mut self, env: &mut E, max_steps: usize) -> f64 
+    where E : Environment
+  {
+    let mut obs = env.reset();
+    let mut total = 0f64;
+
+    for _ in 0..max_steps {
+      let action = self.act(&obs);
+      let step = env.step(action);
+
+      total += step.reward;
+
+      self.remember(Transition {
+        observation: obs,
+        action: action,
+        reward: step.reward,
+        next: step.observation.clone(),
+        done: step.done
+      });
+      self.replay();
+
+      obs = step.observation;
+
+      if step.done { break }
+    }
+
+    self.epsilon = (self.epsilon * X::epsilon_decay()).max(X::epsilon_min());
+
+    total
+  }
+
+  /// Returns the current probability of taking a random action.
+  ///
+  #[inline(always)] pub fn epsilon(&self) -> f64 { self.epsilon }
+
+  /// Returns a reference to the neural net.
+  ///
+  #[inline(always)] pub fn nnet(&self) -> &N { &self.nnet }
+
+  /// Consumes the agent, returning the neural net.
+  ///
+  #[inline(always)] pub fn into_nnet(self) -> N { self.nnet }
+}
+
+
+/// Trainer for a Q-learning agent, where the stopping condition is the 
+/// number of episodes. Yields the total reward for each episode.
+///
+pub struct DqnTrainer<'a, N : 'a, E : 'a, X : 'a, Y : 'a> {
+  agent: &'a mut DqnAgent<N, X, Y>,
+  env: &'a mut E,
+  episode: usize,
+  max_episodes: usize,
+  max_steps: usize
+}
+
+impl<'a, N, E, X, Y> DqnTrainer<'a, N, E, X, Y>
+  where N : NeuralNet<Y> + Clone, 
+        E : Environment,
+        X : TrainerParameters + QLearningParameters, 
+        Y : NeuralNetParameters
+{
+  /// Creates a new trainer for an agent in an environment, where each 
+  /// episode runs for at most `max_steps` steps.
+  ///
+  /// # Panics
+  ///
+  /// When the number of actions in the environment isn't the dimension of 
+  /// the output layer of the agent's neural net.
+  ///
+  #[inline(always)]
+  pub fn with_episodes(
+    agent: &'a mut DqnAgent<N, X, Y>, 
+    env: &'a mut E, 
+    episodes: usize,
+    max_steps: usize
+  ) -> Self {
+    if env.actions() != N::dim_output() {
+      panic!("neural net should have an output for each action")
+    }
+
+    DqnTrainer {
+      agent: agent,
+      env: env,
+      episode: 0,
+      max_episodes: episodes,
+      max_steps: max_steps
+    }
+  }
+}
+
+impl<'a, N, E, X, Y> NeuralNetTrainer for DqnTrainer<'a, N, E, X, Y>
+  where N : NeuralNet<Y> + Clone, 
+        E : Environment,
+        X : TrainerParameters + QLearningParameters, 
+        Y : NeuralNetParameters
+{ }
+
+impl<'a, N, E, X, Y> Iterator for DqnTrainer<'a, N, E, X, Y>
+  where N : NeuralNet<Y> + Clone, 
+        E : Environment,
+        X : TrainerParameters + QLearningParameters, 
+        Y : NeuralNetParameters
+{
+  type Item = (usize, f64);
+
+  fn next(&mut self) -> Option<(usize, f64)> {
+    if self.episode == self.max_episodes {
+      None
+    } else {
+      let episode = self.episode;
+      let reward = self.agent.episode(self.env, self.max_steps);
+
+      self.episode += 1;
+
+      Some((episode, reward))
+    }
+  }
+}
@@ -0,0 +1,120 @@
+use std::marker::PhantomData;
+
+use prelude::*;
+use trainer::linalg;
+
+
+/// Extreme Learning Machine trainer. The randomly initialized weights from 
+/// the input layer are kept fixed, and the weights from the hidden layer are 
+/// solved for in closed form, by regularized least squares over the hidden 
+/// activations of each member of the training set.
+///
+/// The least squares problem is solved for the weighted sum of each output 
+/// node, so the expected results are mapped through the inverse of the 
+/// activation function (and clamped to its range) first. Yields a single 
+/// item: epoch 0, and the mean squared error over the training set after 
+/// the weights were solved.
+///
+pub struct ElmTrainer<'a, N : 'a, T : 'a, X, Y> {
+  nnet: &'a mut N,
+  tset: &'a [T],
+  done: bool,
+  tptype: PhantomData<X>,
+  nptype: PhantomData<Y>
+}
+
+impl<'a, N, T, X, Y> ElmTrainer<'a, N, T, X, Y> 
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        X : ElmParameters, 
+        Y : NeuralNetParameters,
+        Y::ActivationFunction : InverseActivationFunction
+{
+  /// Creates a new trainer for a neural net, given a training set.
+  ///
+  #[inline(always)]
+  pub fn new(nnet: &'a mut N, tset: &'a [T]) -> Self {
+    ElmTrainer {
+      nnet: nnet,
+      tset: tset,
+      done: false,
+      tptype: PhantomData,
+      nptype: PhantomData
+    }
+  }
+}
+
+impl<'a, N, T, X, Y> NeuralNetTrainer for ElmTrainer<'a, N, T, X, Y> 
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        X : ElmParameters, 
+        Y : NeuralNetParameters,
+        Y::ActivationFunction : InverseActivationFunction
+{ }
+
+impl<'a, N, T, X, Y> Iterator for ElmTrainer<'a, N, T, X, Y> 
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        X : ElmParameters, 
+        Y : NeuralNetParameters,
+        Y::ActivationFunction : InverseActivationFunction
+{
+  type Item = (usize, f64);
+
+  fn next(&mut self) -> Option<(usize, f64)> {
+    if self.done {
+      return None
+    }
+
+    // Hidden activations (including the bias node) are the features, and 
+    // the inverse activations of the expected results the targets.
+    let len = N::dim_hidden() + 1;
+    let mut hth = vec![vec![0f64; len]; len];
+    let mut htt = vec![vec![0f64; N::dim_output()]; len];
+
+    for member in self.tset.iter() {
+      self.nnet.predict(member.input());
+
+      let hidden = self.nnet.layer(Layer::Hidden);
+      let targets: Vec<f64> = member.expected()
+        .iter()
+        .map(|&y| Y::ActivationFunction::inverse(y))
+        .collect();
+
+      for i in 0..len {
+        for j in 0..i + 1 { hth[i][j] += hidden[i] * hidden[j]; }
+        for o in 0..N::dim_output() { htt[i][o] += hidden[i] * targets[o]; }
+      }
+    }
+
+    for i in 0..len {
+      for j in 0..i { hth[j][i] = hth[i][j]; }
+      hth[i][i] += X::regularization();
+    }
+
+    for o in 0..N::dim_output() {
+      let rhs: Vec<f64> = (0..len).map(|i| htt[i][o]).collect();
+
+      match linalg::cholesky_solve(&hth, &rhs) {
+        Some(ws) => {
+          for i in 0..len { *self.nnet.node_mut(Node::WeightHiddenOutput(i, o)) = ws[i]; }
+        }
+        None => warn!("least squares problem is singular, increase the regularization")
+      }
+    }
+
+    let sum = self.tset.iter().fold(0f64, |acc, member| {
+      self.nnet.predict(member.input());
+
+      self.nnet
+        .layer(Layer::Output)
+        .iter()
+        .zip(member.expected().iter())
+        .fold(acc, |acc, (act, exp)| acc + (exp - act) * (exp - act))
+    });
+
+    self.done = true;
+
+    Some((0, sum / (self.tset.len() * N::dim_output()) as f64))
+  }
+}
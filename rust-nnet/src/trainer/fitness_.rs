@@ -0,0 +1,42 @@
+use std::marker::PhantomData;
+
+use prelude::*;
+
+
+/// Fitness of a neural net over a training set, computed as the negative 
+/// average error of its predictions.
+///
+pub struct ErrorFitness<'a, T : 'a, E, P> {
+  tset: &'a [T],
+  etype: PhantomData<E>,
+  ptype: PhantomData<P>
+}
+
+impl<'a, T, E, P> ErrorFitness<'a, T, E, P> 
+  where T : TrainingSetMember,
+        E : ErrorFunction,
+        P : NeuralNetParameters
+{
+  /// Creates a new fitness function over a training set.
+  ///
+  #[inline(always)]
+  pub fn new(tset: &'a [T]) -> ErrorFitness<'a, T, E, P> {
+    ErrorFitness { tset: tset, etype: PhantomData, ptype: PhantomData }
+  }
+}
+
+impl<'a, T, E, P, N> Fitness<N> for ErrorFitness<'a, T, E, P> 
+  where T : TrainingSetMember,
+        E : ErrorFunction,
+        P : NeuralNetParameters,
+        N : Predictor<P>
+{
+  fn fitness(&self, nn: &mut N) -> f64 {
+    let sum = self.tset.iter().fold(0f64, |acc, member| {
+      nn.predict(member.input());
+      acc + E::error(nn.output().iter(), member.expected().iter())
+    });
+
+    -sum / self.tset.len() as f64
+  }
+}
@@ -0,0 +1,158 @@
+use std::marker::PhantomData;
+
+use num_cpus;
+use prelude::*;
+use rand::{thread_rng, Rng};
+use rand::distributions::IndependentSample;
+use rand::distributions::normal::Normal;
+use scoped_threadpool::Pool;
+use trainer::util;
+
+
+/// Genetic algorithm trainer for a neural net with a fixed topology, where 
+/// the stopping condition is the number of generations. Yields the best 
+/// fitness of each generation.
+///
+/// Each generation, parents are picked by tournament selection, and 
+/// offspring are created by uniform crossover of their weights, followed by 
+/// Gaussian mutation. The fitness of the population is computed in parallel. 
+/// The weights of the fittest neural net found so far are copied into the 
+/// trained neural net.
+///
+pub struct GeneticTrainer<'a, N : 'a, F, X, Y> {
+  nnet: &'a mut N,
+  fitness: F,
+  population: Vec<N>,
+  pool: Pool,
+  best: f64,
+  generation: usize,
+  max_generations: usize,
+  tptype: PhantomData<X>,
+  nptype: PhantomData<Y>
+}
+
+impl<'a, N, F, X, Y> GeneticTrainer<'a, N, F, X, Y>
+  where N : NeuralNet<Y> + Clone,
+        F : Fitness<N>,
+        X : GeneticParameters,
+        Y : NeuralNetParameters
+{
+  /// Creates a new trainer for a neural net, given a fitness function, where 
+  /// the stopping condition is the number of generations. The population 
+  /// starts with the neural net, and copies of it with random weights.
+  ///
+  /// # Panics
+  ///
+  /// When the population is smaller than the number of elites, or is empty, 
+  /// or when the tournament size is 0.
+  ///
+  pub fn with_generations(nnet: &'a mut N, fitness: F, generations: usize) -> Self {
+    if X::population() == 0 || X::population() < X::elitism() {
+      panic!("population should be non-empty and hold every elite")
+    }
+    if X::tournament_size() == 0 { panic!("tournament size should be at least 1") }
+
+    let population = (0..X::population())
+      .map(|i| {
+        let mut nn = nnet.clone();
+
+        if i > 0 {
+          let ws: Vec<f64> = (0..util::weight_count::<Y, N>())
+            .map(|_| Y::WeightFunction::initw(N::dim_input(), N::dim_output()))
+            .collect();
+          util::set_weights(&mut nn, &ws);
+        }
+
+        nn
+      })
+      .collect();
+
+    GeneticTrainer {
+      nnet: nnet,
+      fitness: fitness,
+      population: population,
+      pool: Pool::new(num_cpus::get() as u32),
+      best: ::std::f64::NEG_INFINITY,
+      generation: 0,
+      max_generations: generations,
+      tptype: PhantomData,
+      nptype: PhantomData
+    }
+  }
+}
+
+impl<'a, N, F, X, Y> NeuralNetTrainer for GeneticTrainer<'a, N, F, X, Y>
+  where N : NeuralNet<Y> + Send,
+        F : Fitness<N> + Sync,
+        X : GeneticParameters,
+        Y : NeuralNetParameters
+{ }
+
+impl<'a, N, F, X, Y> Iterator for GeneticTrainer<'a, N, F, X, Y>
+  where N : NeuralNet<Y> + Send,
+        F : Fitness<N> + Sync,
+        X : GeneticParameters,
+        Y : NeuralNetParameters
+{
+  type Item = (usize, f64);
+
+  fn next(&mut self) -> Option<(usize, f64)> {
+    if self.generation == self.max_generations {
+      return None
+    }
+
+    let generation = self.generation;
+    let mut rng = thread_rng();
+    let scores = util::evaluate(&mut self.pool, &self.fitness, &mut self.population);
+    let genes: Vec<Vec<f64>> = self.population
+      .iter()
+      .map(|nn| util::weights::<Y, _>(nn))
+      .collect();
+
+    let mut ranked: Vec<usize> = (0..scores.len()).collect();
+    ranked.sort_by(|&a, &b| {
+      scores[b].partial_cmp(&scores[a]).unwrap_or(::std::cmp::Ordering::Equal)
+    });
+
+    let best = scores[ranked[0]];
+
+    if best > self.best {
+      self.best = best;
+      util::set_weights(self.nnet, &genes[ranked[0]]);
+    }
+
+    let normal = Normal::new(0f64, X::mutation_stddev());
+    let tournament = |rng: &mut ::rand::ThreadRng| {
+      (1..X::tournament_size()).fold(rng.gen_range(0, scores.len()), |acc, _| {
+        let i = rng.gen_range(0, scores.len());
+        if scores[i] > scores[acc] { i } else { acc }
+      })
+    };
+
+    for k in 0..self.population.len() {
+      let child = if k < X::elitism() {
+        genes[ranked[k]].clone()
+      } else {
+        let a = &genes[tournament(&mut rng)];
+        let mut child = if rng.gen::<f64>() < X::crossover_rate() {
+          let b = &genes[tournament(&mut rng)];
+          a.iter().zip(b.iter()).map(|(&x, &y)| if rng.gen() { x } else { y }).collect()
+        } else {
+          a.clone()
+        };
+
+        for w in child.iter_mut() {
+          if rng.gen::<f64>() < X::mutation_rate() { *w += normal.ind_sample(&mut rng); }
+        }
+
+        child
+      };
+
+      util::set_weights(&mut self.population[k], &child);
+    }
+
+    self.generation += 1;
+
+    Some((generation, best))
+  }
+}
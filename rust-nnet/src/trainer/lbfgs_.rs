@@ -0,0 +1,191 @@
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+
+use prelude::*;
+use trainer::linalg::dot;
+use trainer::util;
+use trainer::util::TrainerState;
+
+
+/// Limited memory BFGS trainer where the stopping condition is based on a 
+/// max number of epochs. Yields the epoch, and the mean squared error over 
+/// the training set after the weights were updated.
+///
+/// Each epoch computes a search direction for the flattened weights from the 
+/// gradient over the whole training set, and the last few steps and 
+/// gradient changes, then finds a step length with a backtracking (Armijo) 
+/// line search. The memory is cleared whenever the line search fails, or the 
+/// direction isn't a descent direction. Training stops early once the 
+/// gradient vanishes.
+///
+pub struct LbfgsTrainer<'a, N : 'a, T : 'a, X, Y> {
+  nnet: &'a mut N,
+  tset: &'a [T],
+  state: TrainerState,
+  weights: Vec<f64>,
+  gradient: Vec<f64>,
+  error: f64,
+  memory: VecDeque<(Vec<f64>, Vec<f64>, f64)>,
+  epoch: usize,
+  max_epochs: usize,
+  tptype: PhantomData<X>,
+  nptype: PhantomData<Y>
+}
+
+impl<'a, N, T, X, Y> LbfgsTrainer<'a, N, T, X, Y> 
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        X : LbfgsParameters, 
+        Y : NeuralNetParameters
+{
+  /// Creates a new trainer for a neural net, given a training set, where the 
+  /// max number of epochs is set to `::std::usize::MAX`.
+  ///
+  #[inline(always)]
+  pub fn new(nnet: &'a mut N, tset: &'a [T]) -> Self {
+    Self::with_epochs(nnet, tset, ::std::usize::MAX)
+  }
+
+  /// Creates a new trainer for a neural net, given a training set, where the 
+  /// stopping condition is the number of epochs.
+  ///
+  pub fn with_epochs(nnet: &'a mut N, tset: &'a [T], epochs: usize) -> Self {
+    let mut state = TrainerState::new::<_, N>();
+    let error = util::batch_gradient(nnet, &mut state, tset);
+
+    LbfgsTrainer {
+      weights: util::weights::<Y, _>(nnet),
+      gradient: state.gradient().to_vec(),
+      nnet: nnet,
+      tset: tset,
+      state: state,
+      error: error,
+      memory: VecDeque::with_capacity(X::memory()),
+      epoch: 0,
+      max_epochs: epochs,
+      tptype: PhantomData,
+      nptype: PhantomData
+    }
+  }
+
+  /// Returns the search direction, computed with the two-loop recursion.
+  ///
+  fn direction(&self) -> Vec<f64> {
+    let mut q = self.gradient.clone();
+    let mut alphas = Vec::with_capacity(self.memory.len());
+
+    for &(ref s, ref y, rho) in self.memory.iter().rev() {
+      let alpha = rho * dot(s, &q);
+
+      for i in 0..q.len() { q[i] -= alpha * y[i]; }
+      alphas.push(alpha);
+    }
+
+    if let Some(&(ref s, ref y, _)) = self.memory.back() {
+      let gamma = dot(s, y) / dot(y, y);
+
+      for x in q.iter_mut() { *x *= gamma; }
+    }
+
+    for (&(ref s, ref y, rho), alpha) in self.memory.iter().zip(alphas.iter().rev()) {
+      let beta = rho * dot(y, &q);
+
+      for i in 0..q.len() { q[i] += s[i] * (alpha - beta); }
+    }
+
+    q.iter().map(|x| -x).collect()
+  }
+
+  /// Returns the mean squared error, given half the summed squared error.
+  ///
+  #[inline]
+  fn mse(&self, error: f64) -> f64 {
+    2f64 * error / (self.tset.len() * N::dim_output()) as f64
+  }
+}
+
+impl<'a, N, T, X, Y> NeuralNetTrainer for LbfgsTrainer<'a, N, T, X, Y> 
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        X : LbfgsParameters, 
+        Y : NeuralNetParameters
+{ }
+
+impl<'a, N, T, X, Y> Iterator for LbfgsTrainer<'a, N, T, X, Y> 
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        X : LbfgsParameters, 
+        Y : NeuralNetParameters
+{
+  type Item = (usize, f64);
+
+  fn next(&mut self) -> Option<(usize, f64)> {
+    if self.epoch == self.max_epochs {
+      return None
+    }
+
+    let epoch = self.epoch;
+    let mut direction = self.direction();
+    let mut slope = dot(&self.gradient, &direction);
+
+    self.epoch += 1;
+
+    if slope >= 0f64 {
+      self.memory.clear();
+      direction = self.gradient.iter().map(|g| -g).collect();
+      slope = -dot(&self.gradient, &self.gradient);
+    }
+
+    if slope == 0f64 {
+      self.max_epochs = self.epoch;
+      return Some((epoch, self.mse(self.error)))
+    }
+
+    let mut step = if self.memory.is_empty() { 
+      1f64.min(1f64 / (-slope).sqrt()) 
+    } else { 
+      1f64 
+    };
+
+    for _ in 0..X::line_search_steps() {
+      let next: Vec<f64> = self.weights
+        .iter()
+        .zip(direction.iter())
+        .map(|(w, d)| w + step * d)
+        .collect();
+
+      util::set_weights(self.nnet, &next);
+
+      let error = util::batch_gradient(self.nnet, &mut self.state, self.tset);
+
+      if error <= self.error + X::armijo() * step * slope {
+        let s: Vec<f64> = (0..next.len()).map(|i| next[i] - self.weights[i]).collect();
+        let y: Vec<f64> = (0..next.len())
+          .map(|i| self.state.gradient()[i] - self.gradient[i])
+          .collect();
+        let sy = dot(&s, &y);
+
+        // Only keep pairs that preserve the positive definiteness of the 
+        // inverse Hessian approximation.
+        if sy > 1e-10f64 {
+          if self.memory.len() == X::memory() { self.memory.pop_front(); }
+          self.memory.push_back((s, y, 1f64 / sy));
+        }
+
+        self.weights = next;
+        self.gradient = self.state.gradient().to_vec();
+        self.error = error;
+
+        return Some((epoch, self.mse(error)))
+      }
+
+      step *= X::backtrack();
+    }
+
+    // The line search failed, so start over with gradient descent.
+    util::set_weights(self.nnet, &self.weights);
+    self.memory.clear();
+
+    Some((epoch, self.mse(self.error)))
+  }
+}
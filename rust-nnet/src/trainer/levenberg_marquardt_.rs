@@ -0,0 +1,170 @@
+use std::marker::PhantomData;
+
+use prelude::*;
+use trainer::linalg;
+use trainer::util;
+
+
+/// Levenberg-Marquardt trainer where the stopping condition is based on a 
+/// max number of epochs. Yields the epoch, and the mean squared error over 
+/// the training set after the weights were updated.
+///
+/// Each epoch builds the Jacobian of the error of each output, for each 
+/// member of the training set, with respect to every weight, and solves the 
+/// damped normal equations `(J^T * J + damping * I) * step = J^T * e`. The 
+/// damping is lowered after a step that decreases the error, and raised 
+/// (retrying the step) otherwise. Training stops early once the damping 
+/// exceeds its max. The cost of each epoch grows with the square of the 
+/// number of weights, so it is best suited to small networks.
+///
+pub struct LevenbergMarquardtTrainer<'a, N : 'a, T : 'a, X, Y> {
+  nnet: &'a mut N,
+  tset: &'a [T],
+  damping: f64,
+  epoch: usize,
+  max_epochs: usize,
+  tptype: PhantomData<X>,
+  nptype: PhantomData<Y>
+}
+
+impl<'a, N, T, X, Y> LevenbergMarquardtTrainer<'a, N, T, X, Y> 
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        X : LevenbergMarquardtParameters, 
+        Y : NeuralNetParameters
+{
+  /// Creates a new trainer for a neural net, given a training set, where the 
+  /// max number of epochs is set to `::std::usize::MAX`.
+  ///
+  #[inline(always)]
+  pub fn new(nnet: &'a mut N, tset: &'a [T]) -> Self {
+    Self::with_epochs(nnet, tset, ::std::usize::MAX)
+  }
+
+  /// Creates a new trainer for a neural net, given a training set, where the 
+  /// stopping condition is the number of epochs.
+  ///
+  #[inline(always)]
+  pub fn with_epochs(nnet: &'a mut N, tset: &'a [T], epochs: usize) -> Self {
+    LevenbergMarquardtTrainer {
+      nnet: nnet,
+      tset: tset,
+      damping: X::initial_damping(),
+      epoch: 0,
+      max_epochs: epochs,
+      tptype: PhantomData,
+      nptype: PhantomData
+    }
+  }
+
+  /// Returns the current damping factor.
+  ///
+  #[inline(always)] pub fn damping(&self) -> f64 { self.damping }
+
+  /// Returns the mean squared error over the training set.
+  ///
+  fn error(&mut self) -> f64 {
+    let sum = self.tset.iter().fold(0f64, |acc, member| {
+      self.nnet.predict(member.input());
+
+      self.nnet
+        .layer(Layer::Output)
+        .iter()
+        .zip(member.expected().iter())
+        .fold(acc, |acc, (act, exp)| acc + (exp - act) * (exp - act))
+    });
+
+    sum / (self.tset.len() * N::dim_output()) as f64
+  }
+
+  /// Returns `J^T * J`, `J^T * e`, and the mean squared error, where `J` is 
+  /// the Jacobian of the outputs for each member of the training set, and 
+  /// `e` the errors.
+  ///
+  fn normal_equations(&mut self) -> (Vec<Vec<f64>>, Vec<f64>, f64) {
+    let count = util::weight_count::<Y, N>();
+    let mut jtj = vec![vec![0f64; count]; count];
+    let mut jte = vec![0f64; count];
+    let mut row = vec![0f64; count];
+    let mut sum = 0f64;
+
+    for member in self.tset.iter() {
+      self.nnet.predict(member.input());
+
+      for o in 0..N::dim_output() {
+        let e = member.expected()[o] - self.nnet.node(Node::Output(o));
+
+        util::output_gradient(self.nnet, o, &mut row);
+        sum += e * e;
+
+        for i in 0..count {
+          if row[i] == 0f64 { continue }
+
+          jte[i] += row[i] * e;
+
+          for j in 0..i + 1 { jtj[i][j] += row[i] * row[j]; }
+        }
+      }
+    }
+
+    for i in 0..count {
+      for j in 0..i { jtj[j][i] = jtj[i][j]; }
+    }
+
+    (jtj, jte, sum / (self.tset.len() * N::dim_output()) as f64)
+  }
+}
+
+impl<'a, N, T, X, Y> NeuralNetTrainer for LevenbergMarquardtTrainer<'a, N, T, X, Y> 
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        X : LevenbergMarquardtParameters, 
+        Y : NeuralNetParameters
+{ }
+
+impl<'a, N, T, X, Y> Iterator for LevenbergMarquardtTrainer<'a, N, T, X, Y> 
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        X : LevenbergMarquardtParameters, 
+        Y : NeuralNetParameters
+{
+  type Item = (usize, f64);
+
+  fn next(&mut self) -> Option<(usize, f64)> {
+    if self.epoch == self.max_epochs {
+      return None
+    }
+
+    let epoch = self.epoch;
+    let (jtj, jte, err) = self.normal_equations();
+    let ws = util::weights::<Y, _>(self.nnet);
+    let mut damped = jtj.clone();
+
+    self.epoch += 1;
+
+    while self.damping <= X::max_damping() {
+      for i in 0..ws.len() { damped[i][i] = jtj[i][i] + self.damping; }
+
+      if let Some(step) = linalg::cholesky_solve(&damped, &jte) {
+        let next: Vec<f64> = ws.iter().zip(step.iter()).map(|(w, s)| w + s).collect();
+
+        util::set_weights(self.nnet, &next);
+
+        let nerr = self.error();
+
+        if nerr < err {
+          self.damping /= X::damping_factor();
+          return Some((epoch, nerr))
+        }
+      }
+
+      self.damping *= X::damping_factor();
+    }
+
+    // No step decreases the error, so the weights have converged.
+    util::set_weights(self.nnet, &ws);
+    self.max_epochs = self.epoch;
+
+    Some((epoch, err))
+  }
+}
@@ -0,0 +1,96 @@
+/// Computes the eigenvalues and eigenvectors of a symmetric matrix with the 
+/// cyclic Jacobi method. The eigenvectors are the columns of the returned 
+/// matrix.
+///
+pub fn symmetric_eigen(a: &[Vec<f64>]) -> (Vec<f64>, Vec<Vec<f64>>) {
+  let n = a.len();
+  let mut a = a.to_vec();
+  let mut v: Vec<Vec<f64>> = (0..n)
+    .map(|i| (0..n).map(|j| if i == j { 1f64 } else { 0f64 }).collect())
+    .collect();
+
+  for _ in 0..64 {
+    let off = (0..n).fold(0f64, |acc, i| {
+      (i + 1..n).fold(acc, |acc, j| acc + a[i][j] * a[i][j])
+    });
+
+    if off < 1e-30f64 { break }
+
+    for p in 0..n {
+      for q in p + 1..n {
+        if a[p][q] == 0f64 { continue }
+
+        let theta = (a[q][q] - a[p][p]) / (2f64 * a[p][q]);
+        let t = theta.signum() / (theta.abs() + (theta * theta + 1f64).sqrt());
+        let c = 1f64 / (t * t + 1f64).sqrt();
+        let s = t * c;
+
+        for k in 0..n {
+          let (akp, akq) = (a[k][p], a[k][q]);
+          a[k][p] = c * akp - s * akq;
+          a[k][q] = s * akp + c * akq;
+        }
+
+        for k in 0..n {
+          let (apk, aqk) = (a[p][k], a[q][k]);
+          a[p][k] = c * apk - s * aqk;
+          a[q][k] = s * apk + c * aqk;
+        }
+
+        for k in 0..n {
+          let (vkp, vkq) = (v[k][p], v[k][q]);
+          v[k][p] = c * vkp - s * vkq;
+          v[k][q] = s * vkp + c * vkq;
+        }
+      }
+    }
+  }
+
+  ((0..n).map(|i| a[i][i]).collect(), v)
+}
+
+
+/// Solves `a * x = b` for a symmetric positive definite matrix with the 
+/// Cholesky decomposition. Returns `None` if the matrix isn't positive 
+/// definite.
+///
+pub fn cholesky_solve(a: &[Vec<f64>], b: &[f64]) -> Option<Vec<f64>> {
+  let n = a.len();
+  let mut l = vec![vec![0f64; n]; n];
+
+  for i in 0..n {
+    for j in 0..i + 1 {
+      let sum = (0..j).fold(a[i][j], |acc, k| acc - l[i][k] * l[j][k]);
+
+      if i == j {
+        if sum <= 0f64 || !sum.is_finite() { return None }
+        l[i][i] = sum.sqrt();
+      } else {
+        l[i][j] = sum / l[j][j];
+      }
+    }
+  }
+
+  // Forward substitution (L * y = b), then back substitution (L^T * x = y).
+  let mut y = vec![0f64; n];
+
+  for i in 0..n {
+    y[i] = (0..i).fold(b[i], |acc, k| acc - l[i][k] * y[k]) / l[i][i];
+  }
+
+  let mut x = vec![0f64; n];
+
+  for i in (0..n).rev() {
+    x[i] = (i + 1..n).fold(y[i], |acc, k| acc - l[k][i] * x[k]) / l[i][i];
+  }
+
+  Some(x)
+}
+
+
+/// Computes the dot product of two vectors.
+///
+#[inline]
+pub fn dot(a: &[f64], b: &[f64]) -> f64 {
+  a.iter().zip(b.iter()).fold(0f64, |acc, (x, y)| acc + x * y)
+}
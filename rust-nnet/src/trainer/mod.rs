@@ -1,4 +1,23 @@
+mod adversarial_;
+mod annealing_;
 mod backpropagation_;
+mod cascade_;
+mod cmaes_;
+mod conjugate_gradient_;
+mod distillation_;
+mod dqn_;
+mod elm_;
+mod fitness_;
+mod genetic_;
+mod lbfgs_;
+mod levenberg_marquardt_;
+mod linalg;
+mod neat_;
+mod online_;
+mod quickprop_;
+mod reinforce_;
+mod rprop_;
+mod swarm_;
 mod util;
 
 /// Implementation of backpropagation trainers.
@@ -7,7 +26,8 @@ pub mod backpropagation {
   pub use trainer::backpropagation_::{
     SeqEpochTrainer, 
     SeqErrorAverageTrainer,
-    BatchEpochTrainer
+    BatchEpochTrainer,
+    DenoisingTrainer
   };
 
   /// Multithreaded implementations of backpropagation trainers.
@@ -16,4 +36,145 @@ pub mod backpropagation {
     pub use trainer::backpropagation_::BatchEpochTrainerParallel 
          as BatchEpochTrainer;
   }
+
+  /// Implementations of backpropagation trainers that draw the training set 
+  /// from a re-iterable source instead of a slice. The source is any 
+  /// `FnMut() -> I`, where `I` is an iterator of `TrainingSetMember`s, 
+  /// and is called once per epoch.
+  ///
+  pub mod stream {
+    pub use trainer::backpropagation_::{
+      SeqEpochTrainerStream as SeqEpochTrainer,
+      SeqErrorAverageTrainerStream as SeqErrorAverageTrainer,
+      BatchEpochTrainerStream as BatchEpochTrainer
+    };
+  }
+}
+
+/// Learner that updates a neural net one example at a time.
+///
+pub mod online {
+  pub use trainer::online_::OnlineLearner;
+}
+
+/// Trainer that compresses a neural net into another, usually smaller, 
+/// neural net.
+///
+pub mod distillation {
+  pub use trainer::distillation_::DistillationTrainer;
+}
+
+/// Adversarial example generation, and adversarial training.
+///
+pub mod adversarial {
+  pub use trainer::adversarial_::{
+    Attack,
+    AdversarialTrainer,
+    loss_gradient,
+    fgsm,
+    pgd,
+    augment
+  };
+}
+
+/// Reinforcement learning agents, and their trainers. See 
+/// `environment::Environment`.
+///
+pub mod reinforcement {
+  pub use trainer::dqn_::{DqnAgent, DqnTrainer, Transition};
+  pub use trainer::reinforce_::ReinforceTrainer;
+}
+
+/// NeuroEvolution of Augmenting Topologies (NEAT).
+///
+pub mod neat {
+  pub use trainer::neat_::{
+    ConnectionGene,
+    Genome,
+    NeatNetwork,
+    NeatTrainer
+  };
+}
+
+/// Fitness functions for gradient-free trainers.
+///
+pub mod fitness {
+  pub use trainer::fitness_::ErrorFitness;
+}
+
+/// Genetic algorithm trainer for neural nets with a fixed topology.
+///
+pub mod genetic {
+  pub use trainer::genetic_::GeneticTrainer;
+}
+
+/// Particle swarm optimization trainer.
+///
+pub mod swarm {
+  pub use trainer::swarm_::SwarmTrainer;
+}
+
+/// Covariance matrix adaptation evolution strategy (CMA-ES) trainer.
+///
+pub mod cmaes {
+  pub use trainer::cmaes_::CmaEsTrainer;
+}
+
+/// Simulated annealing trainer.
+///
+pub mod annealing {
+  pub use trainer::annealing_::AnnealingTrainer;
+}
+
+/// Resilient backpropagation (RPROP) trainers.
+///
+pub mod rprop {
+  pub use trainer::rprop_::{
+    RpropVariant,
+    RpropPlus,
+    RpropMinus,
+    IRpropPlus,
+    IRpropMinus,
+    RpropTrainer,
+    RpropPlusTrainer,
+    RpropMinusTrainer,
+    IRpropPlusTrainer,
+    IRpropMinusTrainer
+  };
+}
+
+/// Levenberg-Marquardt trainer.
+///
+pub mod levenberg_marquardt {
+  pub use trainer::levenberg_marquardt_::LevenbergMarquardtTrainer;
+}
+
+/// Quickprop trainer.
+///
+pub mod quickprop {
+  pub use trainer::quickprop_::QuickpropTrainer;
+}
+
+/// Scaled conjugate gradient trainer.
+///
+pub mod conjugate_gradient {
+  pub use trainer::conjugate_gradient_::ScaledConjugateGradientTrainer;
+}
+
+/// Limited memory BFGS trainer.
+///
+pub mod lbfgs {
+  pub use trainer::lbfgs_::LbfgsTrainer;
+}
+
+/// Cascade-Correlation constructive trainer.
+///
+pub mod cascade {
+  pub use trainer::cascade_::{CascadeNetwork, CascadeTrainer};
+}
+
+/// Extreme Learning Machine trainer.
+///
+pub mod elm {
+  pub use trainer::elm_::ElmTrainer;
 }
\ No newline at end of file
@@ -0,0 +1,714 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use prelude::*;
+use rand::{thread_rng, Rng};
+use rand::distributions::IndependentSample;
+use rand::distributions::normal::Normal;
+use rand::distributions::range::Range;
+
+
+/// A connection between two nodes in a genome.
+///
+#[derive(Copy, Clone, Debug, RustcEncodable, RustcDecodable)]
+pub struct ConnectionGene {
+  /// Id of the node the connection starts at.
+  ///
+  pub from: usize,
+
+  /// Id of the node the connection ends at.
+  ///
+  pub to: usize,
+
+  #[allow(missing_docs)] pub weight: f64,
+  #[allow(missing_docs)] pub enabled: bool,
+
+  /// Historical marking of the connection. Connections between the same 
+  /// nodes share the same innovation number across genomes.
+  ///
+  pub innovation: usize
+}
+
+
+/// Encoding of a neural net with an arbitrary (feed forward) topology. 
+///
+/// Node ids `0..inputs` are the input nodes, `inputs` is the bias node, and 
+/// the next `outputs` ids are the output nodes. Every other id is a hidden 
+/// node.
+///
+#[derive(Clone, Debug, RustcEncodable, RustcDecodable)]
+pub struct Genome {
+  inputs: usize,
+  outputs: usize,
+  hidden: Vec<usize>,
+  connections: Vec<ConnectionGene>
+}
+
+impl Genome {
+  /// Creates a genome with every input (and the bias) connected to every 
+  /// output, using random weights.
+  ///
+  fn minimal<R>(
+    inputs: usize, 
+    outputs: usize, 
+    innovations: &mut Innovations, 
+    rng: &mut R
+  ) -> Genome where R : Rng {
+    let mut connections = Vec::with_capacity((inputs + 1) * outputs);
+
+    for to in inputs + 1..inputs + 1 + outputs {
+      for from in 0..inputs + 1 {
+        connections.push(ConnectionGene {
+          from: from,
+          to: to,
+          weight: random_weight(rng),
+          enabled: true,
+          innovation: innovations.connection(from, to)
+        });
+      }
+    }
+
+    connections.sort_by(|a, b| a.innovation.cmp(&b.innovation));
+
+    Genome { 
+      inputs: inputs, 
+      outputs: outputs, 
+      hidden: Vec::new(), 
+      connections: connections 
+    }
+  }
+
+  /// Returns the number of input nodes (excluding the bias node).
+  ///
+  #[inline(always)] pub fn inputs(&self) -> usize { self.inputs }
+
+  /// Returns the number of output nodes.
+  ///
+  #[inline(always)] pub fn outputs(&self) -> usize { self.outputs }
+
+  /// Returns the ids of the hidden nodes.
+  ///
+  #[inline(always)] pub fn hidden(&self) -> &[usize] { &self.hidden }
+
+  /// Returns the connections, ordered by innovation number.
+  ///
+  #[inline(always)] 
+  pub fn connections(&self) -> &[ConnectionGene] { &self.connections }
+
+  /// Builds a runnable neural net from the genome.
+  ///
+  /// # Panics
+  ///
+  /// When the dimensions of the genome don't match `D`.
+  ///
+  pub fn network<P, D>(&self) -> NeatNetwork<P, D> 
+    where P : NeuralNetParameters,
+          D : Dimensions
+  {
+    if self.inputs != D::inputs() || self.outputs != D::outputs() {
+      panic!("genome dimensions should match the network dimensions")
+    }
+
+    let fixed = self.inputs + 1 + self.outputs;
+    let len = fixed + self.hidden.len();
+    let mut slots = HashMap::with_capacity(len);
+
+    for id in 0..fixed { slots.insert(id, id); }
+    for (i, &id) in self.hidden.iter().enumerate() { slots.insert(id, fixed + i); }
+
+    let mut incoming: Vec<Vec<(usize, f64)>> = (0..len).map(|_| Vec::new()).collect();
+    let mut outgoing: Vec<Vec<usize>> = (0..len).map(|_| Vec::new()).collect();
+    let mut indegree = vec![0usize; len];
+
+    for c in self.connections.iter().filter(|c| c.enabled) {
+      let from = slots[&c.from];
+      let to = slots[&c.to];
+
+      incoming[to].push((from, c.weight));
+      outgoing[from].push(to);
+      indegree[to] += 1;
+    }
+
+    // Topological order of the nodes (Kahn's algorithm). Input and bias 
+    // nodes are set by `predict`, so they are left out of the order.
+    let mut order = Vec::with_capacity(len);
+    let mut queue: Vec<usize> = (0..len).filter(|&s| indegree[s] == 0).collect();
+
+    while let Some(s) = queue.pop() {
+      if s > self.inputs { order.push(s); }
+
+      for &t in outgoing[s].iter() {
+        indegree[t] -= 1;
+        if indegree[t] == 0 { queue.push(t); }
+      }
+    }
+
+    let mut values = vec![0f64; len];
+    values[self.inputs] = P::BiasWeightFunction::biasw();
+
+    NeatNetwork {
+      values: values,
+      incoming: incoming,
+      order: order,
+      ptype: PhantomData,
+      dtype: PhantomData
+    }
+  }
+
+  /// Computes the compatibility distance between two genomes.
+  ///
+  fn distance<X>(&self, other: &Genome) -> f64 where X : NeatParameters {
+    let (mut i, mut j) = (0, 0);
+    let (mut disjoint, mut matching, mut wdiff) = (0f64, 0f64, 0f64);
+    let (a, b) = (&self.connections, &other.connections);
+
+    while i < a.len() && j < b.len() {
+      if a[i].innovation == b[j].innovation {
+        matching += 1f64;
+        wdiff += (a[i].weight - b[j].weight).abs();
+        i += 1;
+        j += 1;
+      } else if a[i].innovation < b[j].innovation {
+        disjoint += 1f64;
+        i += 1;
+      } else {
+        disjoint += 1f64;
+        j += 1;
+      }
+    }
+
+    let excess = ((a.len() - i) + (b.len() - j)) as f64;
+    let longest = ::std::cmp::max(a.len(), b.len());
+    let n = if longest < 20 { 1f64 } else { longest as f64 };
+    let wavg = if matching > 0f64 { wdiff / matching } else { 0f64 };
+
+    X::excess_coefficient() * excess / n + 
+      X::disjoint_coefficient() * disjoint / n + 
+      X::weight_coefficient() * wavg
+  }
+
+  /// Creates an offspring from two parents, where `fitter` is the parent with 
+  /// the higher fitness. Matching genes are inherited randomly from either 
+  /// parent, and disjoint and excess genes are inherited from the fitter 
+  /// parent. A gene disabled in either parent is likely to stay disabled.
+  ///
+  fn crossover<R>(fitter: &Genome, other: &Genome, rng: &mut R) -> Genome 
+    where R : Rng 
+  {
+    let mut child = fitter.clone();
+    let mut j = 0;
+
+    for c in child.connections.iter_mut() {
+      while j < other.connections.len() && 
+            other.connections[j].innovation < c.innovation 
+      {
+        j += 1;
+      }
+
+      if j < other.connections.len() && 
+         other.connections[j].innovation == c.innovation 
+      {
+        let o = &other.connections[j];
+
+        if rng.gen::<bool>() { c.weight = o.weight; }
+        if !c.enabled || !o.enabled { c.enabled = rng.gen::<f64>() >= 0.75f64; }
+      }
+    }
+
+    child
+  }
+
+  /// Mutates the weights and structure of a genome.
+  ///
+  fn mutate<X, R>(&mut self, innovations: &mut Innovations, rng: &mut R) 
+    where X : NeatParameters, 
+          R : Rng 
+  {
+    if rng.gen::<f64>() < X::weight_mutation_rate() {
+      let normal = Normal::new(0f64, X::weight_perturbation());
+
+      for c in self.connections.iter_mut() {
+        if rng.gen::<f64>() < X::weight_replace_rate() {
+          c.weight = random_weight(rng);
+        } else {
+          c.weight += normal.ind_sample(rng);
+        }
+      }
+    }
+
+    if rng.gen::<f64>() < X::add_connection_rate() { 
+      self.add_connection(innovations, rng); 
+    }
+
+    if rng.gen::<f64>() < X::add_node_rate() { 
+      self.add_node(innovations, rng); 
+    }
+  }
+
+  /// Adds a connection between two unconnected nodes, that doesn't create a 
+  /// cycle. Gives up after a few attempts.
+  ///
+  fn add_connection<R>(&mut self, innovations: &mut Innovations, rng: &mut R) 
+    where R : Rng 
+  {
+    let sources = self.inputs + 1 + self.hidden.len();
+    let targets = self.outputs + self.hidden.len();
+
+    for _ in 0..20 {
+      let from = match rng.gen_range(0, sources) {
+        s if s <= self.inputs => s,
+        s => self.hidden[s - self.inputs - 1]
+      };
+      let to = match rng.gen_range(0, targets) {
+        t if t < self.outputs => self.inputs + 1 + t,
+        t => self.hidden[t - self.outputs]
+      };
+
+      if from == to || 
+         self.connections.iter().any(|c| c.from == from && c.to == to) ||
+         self.reaches(to, from) 
+      {
+        continue
+      }
+
+      self.insert(ConnectionGene {
+        from: from,
+        to: to,
+        weight: random_weight(rng),
+        enabled: true,
+        innovation: innovations.connection(from, to)
+      });
+
+      return
+    }
+  }
+
+  /// Splits a random enabled connection with a new node. The connection 
+  /// into the new node has a weight of 1, and the connection out of it has 
+  /// the weight of the split connection.
+  ///
+  fn add_node<R>(&mut self, innovations: &mut Innovations, rng: &mut R) 
+    where R : Rng 
+  {
+    let enabled: Vec<usize> = (0..self.connections.len())
+      .filter(|&i| self.connections[i].enabled)
+      .collect();
+
+    if enabled.is_empty() { return }
+
+    let split = self.connections[enabled[rng.gen_range(0, enabled.len())]];
+    let mut node = innovations.split(split.innovation);
+
+    if self.hidden.contains(&node) { node = innovations.node(); }
+
+    for c in self.connections.iter_mut() {
+      if c.innovation == split.innovation { c.enabled = false; }
+    }
+
+    self.hidden.push(node);
+    self.insert(ConnectionGene {
+      from: split.from,
+      to: node,
+      weight: 1f64,
+      enabled: true,
+      innovation: innovations.connection(split.from, node)
+    });
+    self.insert(ConnectionGene {
+      from: node,
+      to: split.to,
+      weight: split.weight,
+      enabled: true,
+      innovation: innovations.connection(node, split.to)
+    });
+  }
+
+  /// Inserts a connection, keeping the connections ordered by innovation 
+  /// number.
+  ///
+  fn insert(&mut self, c: ConnectionGene) {
+    let i = self.connections
+      .iter()
+      .position(|o| o.innovation > c.innovation)
+      .unwrap_or(self.connections.len());
+
+    self.connections.insert(i, c);
+  }
+
+  /// Returns whether there is a path from one node to another.
+  ///
+  fn reaches(&self, from: usize, to: usize) -> bool {
+    let mut stack = vec![from];
+    let mut seen = vec![from];
+
+    while let Some(n) = stack.pop() {
+      if n == to { return true }
+
+      for c in self.connections.iter().filter(|c| c.from == n) {
+        if !seen.contains(&c.to) {
+          seen.push(c.to);
+          stack.push(c.to);
+        }
+      }
+    }
+
+    false
+  }
+}
+
+
+#[inline]
+fn random_weight<R>(rng: &mut R) -> f64 where R : Rng {
+  Range::new(-1f64, 1f64).ind_sample(rng)
+}
+
+
+/// Innovation numbers of each connection, and ids of each node created by 
+/// splitting a connection, so the same structural mutation gets the same 
+/// historical marking in every genome.
+///
+struct Innovations {
+  connections: HashMap<(usize, usize), usize>,
+  splits: HashMap<usize, usize>,
+  next_innovation: usize,
+  next_node: usize
+}
+
+impl Innovations {
+  fn new(nodes: usize) -> Innovations {
+    Innovations {
+      connections: HashMap::new(),
+      splits: HashMap::new(),
+      next_innovation: 0,
+      next_node: nodes
+    }
+  }
+
+  fn connection(&mut self, from: usize, to: usize) -> usize {
+    let next = &mut self.next_innovation;
+
+    *self.connections.entry((from, to)).or_insert_with(|| { 
+      *next += 1; 
+      *next - 1 
+    })
+  }
+
+  fn split(&mut self, innovation: usize) -> usize {
+    let next = &mut self.next_node;
+
+    *self.splits.entry(innovation).or_insert_with(|| { 
+      *next += 1; 
+      *next - 1 
+    })
+  }
+
+  fn node(&mut self) -> usize {
+    self.next_node += 1;
+    self.next_node - 1
+  }
+}
+
+
+/// A neural net built from a genome. 
+///
+/// The hidden nodes don't form a single layer, so the network only 
+/// implements `Predictor`, and not `NeuralNet`. The activation of each 
+/// hidden node is available through `hidden`. 
+///
+/// The network can be evaluated wherever only predictions are needed (such 
+/// as with `ErrorFitness`), but it can't be passed to the trainers, agents, 
+/// or functions that take a `NeuralNet`, as they need the weights of a 
+/// single hidden layer.
+///
+#[derive(Clone, RustcEncodable, RustcDecodable)]
+pub struct NeatNetwork<P, D> {
+  values: Vec<f64>,
+  incoming: Vec<Vec<(usize, f64)>>,
+  order: Vec<usize>,
+  ptype: PhantomData<P>,
+  dtype: PhantomData<D>
+}
+
+impl<P, D> NeatNetwork<P, D> where D : Dimensions {
+  /// Returns the activation of each hidden node, from the last prediction.
+  ///
+  #[inline(always)] 
+  pub fn hidden(&self) -> &[f64] { &self.values[D::inputs() + 1 + D::outputs()..] }
+}
+
+impl<P, D> Predictor<P> for NeatNetwork<P, D> 
+  where P : NeuralNetParameters,
+        D : Dimensions
+{
+  #[inline(always)] fn dim_input() -> usize { D::inputs() }
+
+  #[inline(always)] fn dim_output() -> usize { D::outputs() }
+
+  fn predict(&mut self, inp: &[f64]) {
+    assert!(inp.len() == D::inputs());
+
+    for i in 0..D::inputs() { self.values[i] = inp[i]; }
+
+    for k in 0..self.order.len() {
+      let s = self.order[k];
+      let sum = self.incoming[s]
+        .iter()
+        .fold(0f64, |acc, &(j, w)| acc + self.values[j] * w);
+
+      self.values[s] = P::ActivationFunction::activation(sum);
+    }
+  }
+
+  #[inline]
+  fn output(&self) -> &[f64] { 
+    let outputs = D::inputs() + 1;
+    &self.values[outputs..outputs + D::outputs()] 
+  }
+}
+
+
+/// A group of genomes with similar topologies. Genomes only compete for 
+/// offspring with other genomes in the same species.
+///
+struct Species {
+  representative: Genome,
+  members: Vec<usize>,
+  best: f64,
+  stagnant: usize
+}
+
+
+/// NeuroEvolution of Augmenting Topologies (NEAT) trainer, where the 
+/// stopping condition is the number of generations. Yields the best fitness 
+/// of each generation.
+///
+/// The population starts with minimal genomes (every input connected to 
+/// every output), and topologies are grown through mutations that add nodes 
+/// and connections. Genomes are grouped into species by compatibility 
+/// distance, and each species is given offspring in proportion to the 
+/// (shared) fitness of its members.
+///
+pub struct NeatTrainer<F, X, Y, D> {
+  fitness: F,
+  population: Vec<Genome>,
+  species: Vec<Species>,
+  innovations: Innovations,
+  best: Option<(Genome, f64)>,
+  generation: usize,
+  max_generations: usize,
+  tptype: PhantomData<X>,
+  nptype: PhantomData<Y>,
+  dtype: PhantomData<D>
+}
+
+impl<F, X, Y, D> NeatTrainer<F, X, Y, D>
+  where F : Fitness<NeatNetwork<Y, D>>,
+        X : NeatParameters,
+        Y : NeuralNetParameters,
+        D : Dimensions
+{
+  /// Creates a new trainer, given a fitness function, where the stopping 
+  /// condition is the number of generations.
+  ///
+  pub fn with_generations(fitness: F, generations: usize) -> Self {
+    let mut rng = thread_rng();
+    let mut innovations = Innovations::new(D::inputs() + 1 + D::outputs());
+    let population = (0..X::population())
+      .map(|_| Genome::minimal(D::inputs(), D::outputs(), &mut innovations, &mut rng))
+      .collect();
+
+    NeatTrainer {
+      fitness: fitness,
+      population: population,
+      species: Vec::new(),
+      innovations: innovations,
+      best: None,
+      generation: 0,
+      max_generations: generations,
+      tptype: PhantomData,
+      nptype: PhantomData,
+      dtype: PhantomData
+    }
+  }
+
+  /// Returns the fittest genome found so far, and its fitness.
+  ///
+  #[inline]
+  pub fn best(&self) -> Option<(&Genome, f64)> {
+    self.best.as_ref().map(|&(ref g, f)| (g, f))
+  }
+
+  /// Returns the number of species in the last generation.
+  ///
+  #[inline(always)] pub fn species(&self) -> usize { self.species.len() }
+
+  /// Assigns each genome to the first species it is compatible with, 
+  /// creating new species as needed. Empty species are removed.
+  ///
+  fn speciate(&mut self) {
+    for s in self.species.iter_mut() { s.members.clear(); }
+
+    for (i, g) in self.population.iter().enumerate() {
+      let found = self.species
+        .iter()
+        .position(|s| g.distance::<X>(&s.representative) < X::compatibility_threshold());
+
+      match found {
+        Some(s) => self.species[s].members.push(i),
+        None => self.species.push(Species { 
+          representative: g.clone(), 
+          members: vec![i], 
+          best: ::std::f64::NEG_INFINITY, 
+          stagnant: 0 
+        })
+      }
+    }
+
+    self.species.retain(|s| !s.members.is_empty());
+  }
+
+  /// Returns the number of offspring for each species, in proportion to the 
+  /// sum of the shared fitness of its members.
+  ///
+  fn offspring(&self, fitness: &[f64]) -> Vec<usize> {
+    let min = fitness.iter().fold(::std::f64::INFINITY, |acc, &f| acc.min(f));
+    let shares: Vec<f64> = self.species
+      .iter()
+      .map(|s| {
+        s.members.iter().fold(0f64, |acc, &i| acc + fitness[i] - min + 1e-3f64) / 
+          s.members.len() as f64
+      })
+      .collect();
+    let total = shares.iter().fold(0f64, |acc, s| acc + s);
+    let quotas: Vec<f64> = shares
+      .iter()
+      .map(|s| s / total * X::population() as f64)
+      .collect();
+    let mut counts: Vec<usize> = quotas.iter().map(|q| q.floor() as usize).collect();
+    let mut remaining = X::population() - counts.iter().fold(0, |acc, c| acc + c);
+
+    // Give the remaining offspring to the species with the largest 
+    // remainders.
+    let mut order: Vec<usize> = (0..counts.len()).collect();
+    order.sort_by(|&a, &b| {
+      (quotas[b] - quotas[b].floor())
+        .partial_cmp(&(quotas[a] - quotas[a].floor()))
+        .unwrap_or(::std::cmp::Ordering::Equal)
+    });
+
+    for &s in order.iter().cycle() {
+      if remaining == 0 { break }
+      counts[s] += 1;
+      remaining -= 1;
+    }
+
+    counts
+  }
+}
+
+impl<F, X, Y, D> NeuralNetTrainer for NeatTrainer<F, X, Y, D>
+  where F : Fitness<NeatNetwork<Y, D>>,
+        X : NeatParameters,
+        Y : NeuralNetParameters,
+        D : Dimensions
+{ }
+
+impl<F, X, Y, D> Iterator for NeatTrainer<F, X, Y, D>
+  where F : Fitness<NeatNetwork<Y, D>>,
+        X : NeatParameters,
+        Y : NeuralNetParameters,
+        D : Dimensions
+{
+  type Item = (usize, f64);
+
+  fn next(&mut self) -> Option<(usize, f64)> {
+    if self.generation == self.max_generations {
+      return None
+    }
+
+    let generation = self.generation;
+    let mut rng = thread_rng();
+
+    let fitness: Vec<f64> = self.population
+      .iter()
+      .map(|g| self.fitness.fitness(&mut g.network::<Y, D>()))
+      .collect();
+
+    let champion = (0..fitness.len()).fold(0, |acc, i| {
+      if fitness[i] > fitness[acc] { i } else { acc }
+    });
+
+    let improved = match self.best {
+      Some((_, f)) => fitness[champion] > f,
+      None => true
+    };
+
+    if improved {
+      self.best = Some((self.population[champion].clone(), fitness[champion]));
+    }
+
+    self.speciate();
+
+    for s in self.species.iter_mut() {
+      let best = s.members.iter().fold(::std::f64::NEG_INFINITY, |acc, &i| acc.max(fitness[i]));
+
+      if best > s.best {
+        s.best = best;
+        s.stagnant = 0;
+      } else {
+        s.stagnant += 1;
+      }
+    }
+
+    // Remove stagnant species, unless they hold the champion.
+    self.species.retain(|s| {
+      s.stagnant <= X::stagnation_limit() || s.members.contains(&champion)
+    });
+
+    let counts = self.offspring(&fitness);
+    let mut population = Vec::with_capacity(X::population());
+
+    for (s, &count) in self.species.iter_mut().zip(counts.iter()) {
+      let mut members = s.members.clone();
+
+      members.sort_by(|&a, &b| {
+        fitness[b].partial_cmp(&fitness[a]).unwrap_or(::std::cmp::Ordering::Equal)
+      });
+
+      let survivors = ::std::cmp::max(
+        (X::survival_threshold() * members.len() as f64).ceil() as usize, 1);
+      let parents = &members[..survivors];
+      let mut count = count;
+
+      // The champion of each large species is copied unchanged.
+      if count > 0 && members.len() > 5 {
+        population.push(self.population[members[0]].clone());
+        count -= 1;
+      }
+
+      for _ in 0..count {
+        let a = parents[rng.gen_range(0, parents.len())];
+        let mut child = if parents.len() > 1 && rng.gen::<f64>() < X::crossover_rate() {
+          let b = parents[rng.gen_range(0, parents.len())];
+
+          if fitness[a] >= fitness[b] {
+            Genome::crossover(&self.population[a], &self.population[b], &mut rng)
+          } else {
+            Genome::crossover(&self.population[b], &self.population[a], &mut rng)
+          }
+        } else {
+          self.population[a].clone()
+        };
+
+        child.mutate::<X, _>(&mut self.innovations, &mut rng);
+        population.push(child);
+      }
+
+      s.representative = self.population[members[rng.gen_range(0, members.len())]].clone();
+    }
+
+    self.population = population;
+    self.generation += 1;
+
+    Some((generation, fitness[champion]))
+  }
+}
@@ -0,0 +1,100 @@
+use std::marker::PhantomData;
+
+use prelude::*;
+use trainer::util;
+use trainer::util::TrainerState;
+
+
+/// Back-propagation learner that owns a neural net, and updates it one 
+/// example at a time as examples arrive. The trainer state (momentum) is 
+/// kept between calls to `learn`.
+///
+/// An optional decay factor can be set to gradually forget old examples. 
+/// Before each update, every weight is scaled by `1 - decay`, which lets the 
+/// neural net track a target that drifts over time.
+///
+pub struct OnlineLearner<N, X, Y> {
+  nnet: N,
+  state: TrainerState,
+  decay: f64,
+  tptype: PhantomData<X>,
+  nptype: PhantomData<Y>
+}
+
+impl<N, X, Y> OnlineLearner<N, X, Y>
+  where N : NeuralNet<Y>, 
+        X : TrainerParametersWithErrorFunction, 
+        Y : NeuralNetParameters
+{
+  /// Creates a new learner that takes ownership of a neural net. Nothing is 
+  /// forgotten between updates.
+  ///
+  #[inline(always)]
+  pub fn new(nnet: N) -> Self {
+    Self::with_decay(nnet, 0f64)
+  }
+
+  /// Creates a new learner that takes ownership of a neural net, and 
+  /// decays the weights by `decay` before each update.
+  ///
+  /// # Panics
+  ///
+  /// When `decay` is not in the range `[0, 1)`.
+  ///
+  #[inline(always)]
+  pub fn with_decay(nnet: N, decay: f64) -> Self {
+    if decay < 0f64 || decay >= 1f64 { 
+      panic!("decay should be in the range [0, 1)") 
+    }
+
+    OnlineLearner {
+      nnet: nnet,
+      state: TrainerState::new::<_, N>(),
+      decay: decay,
+      tptype: PhantomData,
+      nptype: PhantomData
+    }
+  }
+
+  /// Updates the neural net with a single example, and returns the loss of 
+  /// the prediction made for the example before the update was applied.
+  ///
+  pub fn learn<M>(&mut self, member: &M) -> f64 where M : TrainingSetMember {
+    util::update_state::<X, Y, _, _>(&mut self.nnet, &mut self.state, member);
+
+    let loss = {
+      let exp = member.expected();
+      let act = self.nnet.layer(Layer::Output);
+
+      X::ErrorFunction::error(act.iter(), exp.iter())
+    };
+
+    if self.decay > 0f64 {
+      util::scale_weights(&mut self.nnet, 1f64 - self.decay);
+    }
+
+    util::update_weights(&mut self.nnet, &self.state);
+
+    loss
+  }
+
+  /// Computes the prediction of the neural net for an input.
+  ///
+  #[inline]
+  pub fn predict(&mut self, inp: &[f64]) -> &[f64] {
+    self.nnet.predict(inp);
+    self.nnet.layer(Layer::Output)
+  }
+
+  /// Returns a reference to the neural net.
+  ///
+  #[inline(always)] pub fn nnet(&self) -> &N { &self.nnet }
+
+  /// Returns a mutable reference to the neural net.
+  ///
+  #[inline(always)] pub fn nnet_mut(&mut self) -> &mut N { &mut self.nnet }
+
+  /// Consumes the learner, returning the neural net.
+  ///
+  #[inline(always)] pub fn into_nnet(self) -> N { self.nnet }
+}
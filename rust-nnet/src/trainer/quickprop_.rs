@@ -0,0 +1,148 @@
+use std::marker::PhantomData;
+
+use prelude::*;
+use trainer::util;
+use trainer::util::TrainerState;
+
+
+/// Below this, the previous step of a weight is treated as zero.
+///
+const MIN_STEP: f64 = 0.001f64;
+
+
+/// Returns the step to the minimum of the parabola through the current and 
+/// previous slope, limited to `mu` times the previous step.
+///
+#[inline]
+fn parabola(prev: f64, slope: f64, pslope: f64, mu: f64) -> f64 {
+  let step = prev * slope / (pslope - slope);
+  let max = mu * prev.abs();
+
+  if step.is_finite() { step.max(-max).min(max) } else { mu * prev }
+}
+
+
+/// Quickprop trainer where the stopping condition is based on a max number 
+/// of epochs. Yields the epoch, and the mean squared error over the training 
+/// set before the weights were updated.
+///
+/// The gradient is accumulated over the whole training set. Each weight 
+/// jumps to the minimum of a parabola fitted through its current and 
+/// previous gradient, and its previous step. A step is never more than 
+/// `max_growth` times larger than the previous step. Gradient descent is 
+/// used when there is no previous step, or the gradient hasn't changed 
+/// sign.
+///
+pub struct QuickpropTrainer<'a, N : 'a, T : 'a, X, Y> {
+  nnet: &'a mut N,
+  tset: &'a [T],
+  state: TrainerState,
+  changes: Vec<f64>,
+  epoch: usize,
+  max_epochs: usize,
+  tptype: PhantomData<X>,
+  nptype: PhantomData<Y>
+}
+
+impl<'a, N, T, X, Y> QuickpropTrainer<'a, N, T, X, Y> 
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        X : QuickpropParameters, 
+        Y : NeuralNetParameters
+{
+  /// Creates a new trainer for a neural net, given a training set, where the 
+  /// max number of epochs is set to `::std::usize::MAX`.
+  ///
+  #[inline(always)]
+  pub fn new(nnet: &'a mut N, tset: &'a [T]) -> Self {
+    Self::with_epochs(nnet, tset, ::std::usize::MAX)
+  }
+
+  /// Creates a new trainer for a neural net, given a training set, where the 
+  /// stopping condition is the number of epochs.
+  ///
+  #[inline(always)]
+  pub fn with_epochs(nnet: &'a mut N, tset: &'a [T], epochs: usize) -> Self {
+    QuickpropTrainer {
+      nnet: nnet,
+      tset: tset,
+      state: TrainerState::new::<_, N>(),
+      changes: vec![0f64; util::weight_count::<Y, N>()],
+      epoch: 0,
+      max_epochs: epochs,
+      tptype: PhantomData,
+      nptype: PhantomData
+    }
+  }
+}
+
+impl<'a, N, T, X, Y> NeuralNetTrainer for QuickpropTrainer<'a, N, T, X, Y> 
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        X : QuickpropParameters, 
+        Y : NeuralNetParameters
+{ }
+
+impl<'a, N, T, X, Y> Iterator for QuickpropTrainer<'a, N, T, X, Y> 
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        X : QuickpropParameters, 
+        Y : NeuralNetParameters
+{
+  type Item = (usize, f64);
+
+  fn next(&mut self) -> Option<(usize, f64)> {
+    if self.epoch == self.max_epochs {
+      return None
+    }
+
+    let epoch = self.epoch;
+    let len = self.tset.len() as f64;
+
+    self.state.shift_gradient();
+
+    let err = self.tset.iter().fold(0f64, |acc, member| {
+      acc + util::accumulate_gradient(self.nnet, &mut self.state, member)
+    }) / len;
+    let mut ws = util::weights::<Y, _>(self.nnet);
+    let mu = X::max_growth();
+    let shrink = mu / (1f64 + mu);
+
+    for k in 0..ws.len() {
+      // Slopes point downhill (the negative gradient), with weight decay.
+      let slope = -self.state.gradient()[k] / len + X::decay() * ws[k];
+      let pslope = -self.state.pgradient()[k] / len + X::decay() * ws[k];
+      let prev = self.changes[k];
+      let mut step = 0f64;
+
+      if prev > MIN_STEP {
+        if slope > 0f64 { step += X::epsilon() * slope; }
+
+        if slope > shrink * pslope {
+          step += mu * prev;
+        } else {
+          step += parabola(prev, slope, pslope, mu);
+        }
+      } else if prev < -MIN_STEP {
+        if slope < 0f64 { step += X::epsilon() * slope; }
+
+        if slope < shrink * pslope {
+          step += mu * prev;
+        } else {
+          step += parabola(prev, slope, pslope, mu);
+        }
+      } else {
+        step += X::epsilon() * slope;
+      }
+
+      self.changes[k] = step;
+      ws[k] += step;
+    }
+
+    util::set_weights(self.nnet, &ws);
+
+    self.epoch += 1;
+
+    Some((epoch, err))
+  }
+}
@@ -0,0 +1,178 @@
+use std::marker::PhantomData;
+
+use environment::Environment;
+use prelude::*;
+use rand::{thread_rng, Rng};
+use trainer::util;
+use trainer::util::TrainerState;
+
+
+/// Policy gradient trainer (REINFORCE), where the stopping condition is the 
+/// number of episodes. Yields the total reward for each episode.
+///
+/// The neural net is the policy, and should have an output node for each 
+/// action. The probability of each action is the softmax of the output layer, 
+/// divided by the temperature. After each episode, the log probability of 
+/// each action taken is increased in proportion to the discounted return 
+/// that followed it (minus the baseline, if enabled). Weights are updated 
+/// for each step in the episode.
+///
+pub struct ReinforceTrainer<'a, N : 'a, E : 'a, X, Y> {
+  nnet: &'a mut N,
+  env: &'a mut E,
+  state: TrainerState,
+  errors: Vec<f64>,
+  episode: usize,
+  max_episodes: usize,
+  max_steps: usize,
+  tptype: PhantomData<X>,
+  nptype: PhantomData<Y>
+}
+
+impl<'a, N, E, X, Y> ReinforceTrainer<'a, N, E, X, Y>
+  where N : NeuralNet<Y>, 
+        E : Environment,
+        X : TrainerParameters + PolicyGradientParameters, 
+        Y : NeuralNetParameters
+{
+  /// Creates a new trainer for a policy in an environment, where each 
+  /// episode runs for at most `max_steps` steps.
+  ///
+  /// # Panics
+  ///
+  /// When the number of actions in the environment isn't the dimension of 
+  /// the output layer of the neural net.
+  ///
+  #[inline(always)]
+  pub fn with_episodes(
+    nnet: &'a mut N, 
+    env: &'a mut E, 
+    episodes: usize,
+    max_steps: usize
+  ) -> Self {
+    if env.actions() != N::dim_output() {
+      panic!("neural net should have an output for each action")
+    }
+
+    ReinforceTrainer {
+      nnet: nnet,
+      env: env,
+      state: TrainerState::new::<_, N>(),
+      errors: vec![0f64; N::dim_output()],
+      episode: 0,
+      max_episodes: episodes,
+      max_steps: max_steps,
+      tptype: PhantomData,
+      nptype: PhantomData
+    }
+  }
+}
+
+impl<'a, N, E, X, Y> NeuralNetTrainer for ReinforceTrainer<'a, N, E, X, Y>
+  where N : NeuralNet<Y>, 
+        E : Environment,
+        X : TrainerParameters + PolicyGradientParameters, 
+        Y : NeuralNetParameters
+{ }
+
+impl<'a, N, E, X, Y> Iterator for ReinforceTrainer<'a, N, E, X, Y>
+  where N : NeuralNet<Y>, 
+        E : Environment,
+        X : TrainerParameters + PolicyGradientParameters, 
+        Y : NeuralNetParameters
+{
+  type Item = (usize, f64);
+
+  fn next(&mut self) -> Option<(usize, f64)> {
+    if self.episode == self.max_episodes {
+      None
+    } else {
+      let episode = self.episode;
+      let mut rng = thread_rng();
+      let mut steps = Vec::new();
+      let mut obs = self.env.reset();
+
+      for _ in 0..self.max_steps {
+        self.nnet.predict(&obs);
+
+        let probs = policy::<X>(self.nnet.layer(Layer::Output));
+        let action = sample(&probs, rng.gen::<f64>());
+        let step = self.env.step(action);
+
+        steps.push((obs, action, step.reward));
+        obs = step.observation;
+
+        if step.done { break }
+      }
+
+      let total = steps.iter().fold(0f64, |acc, s| acc + s.2);
+
+      // Discounted return following each step.
+      let mut returns = vec![0f64; steps.len()];
+      let mut ret = 0f64;
+
+      for t in (0..steps.len()).rev() {
+        ret = steps[t].2 + X::discount() * ret;
+        returns[t] = ret;
+      }
+
+      let baseline = if X::baseline() && !returns.is_empty() {
+        returns.iter().fold(0f64, |acc, r| acc + r) / returns.len() as f64
+      } else {
+        0f64
+      };
+
+      for (&(ref obs, action, _), ret) in steps.iter().zip(returns.iter()) {
+        self.nnet.predict(obs);
+
+        let probs = policy::<X>(self.nnet.layer(Layer::Output));
+        let advantage = ret - baseline;
+
+        // Gradient of the log probability of the action taken, with respect 
+        // to each output node.
+        for i in 0..N::dim_output() {
+          let taken = if i == action { 1f64 } else { 0f64 };
+          self.errors[i] = advantage * (taken - probs[i]) / X::temperature();
+        }
+
+        util::update_state_with_errors::<X, Y, _>(
+          self.nnet, 
+          &mut self.state, 
+          &self.errors);
+        util::update_weights(self.nnet, &self.state);
+      }
+
+      self.episode += 1;
+
+      Some((episode, total))
+    }
+  }
+}
+
+
+/// Returns the probability of each action, given the output layer.
+///
+fn policy<X>(outputs: &[f64]) -> Vec<f64> where X : PolicyGradientParameters {
+  let max = outputs.iter().fold(::std::f64::NEG_INFINITY, |acc, &o| acc.max(o));
+  let exps: Vec<f64> = outputs
+    .iter()
+    .map(|o| ((o - max) / X::temperature()).exp())
+    .collect();
+  let sum = exps.iter().fold(0f64, |acc, e| acc + e);
+
+  exps.iter().map(|e| e / sum).collect()
+}
+
+
+/// Samples an action from a distribution, given `u` in `[0, 1)`.
+///
+fn sample(probs: &[f64], u: f64) -> usize {
+  let mut acc = 0f64;
+
+  for (i, p) in probs.iter().enumerate() {
+    acc += *p;
+    if u < acc { return i }
+  }
+
+  probs.len() - 1
+}
@@ -0,0 +1,234 @@
+use std::marker::PhantomData;
+
+use prelude::*;
+use trainer::util;
+use trainer::util::TrainerState;
+
+
+/// Behaviour of an RPROP variant when the sign of a weight's gradient 
+/// changes.
+///
+pub trait RpropVariant {
+  /// Returns whether the last change to the weight is reverted, given 
+  /// whether the error increased during the last epoch.
+  ///
+  fn backtrack(increased: bool) -> bool;
+
+  /// Returns whether the weight is left unchanged for the epoch (and its 
+  /// gradient is forgotten), instead of taking a step.
+  ///
+  fn skip() -> bool;
+}
+
+
+/// Rprop with weight backtracking.
+///
+#[derive(Copy, Clone)] pub struct RpropPlus;
+
+impl RpropVariant for RpropPlus {
+  #[inline(always)] fn backtrack(_: bool) -> bool { true }
+  #[inline(always)] fn skip() -> bool { true }
+}
+
+
+/// Rprop without weight backtracking.
+///
+#[derive(Copy, Clone)] pub struct RpropMinus;
+
+impl RpropVariant for RpropMinus {
+  #[inline(always)] fn backtrack(_: bool) -> bool { false }
+  #[inline(always)] fn skip() -> bool { false }
+}
+
+
+/// Improved Rprop, which only backtracks when the error increased.
+///
+#[derive(Copy, Clone)] pub struct IRpropPlus;
+
+impl RpropVariant for IRpropPlus {
+  #[inline(always)] fn backtrack(increased: bool) -> bool { increased }
+  #[inline(always)] fn skip() -> bool { true }
+}
+
+
+/// Improved Rprop without weight backtracking.
+///
+#[derive(Copy, Clone)] pub struct IRpropMinus;
+
+impl RpropVariant for IRpropMinus {
+  #[inline(always)] fn backtrack(_: bool) -> bool { false }
+  #[inline(always)] fn skip() -> bool { true }
+}
+
+
+/// Updates a single weight with the variant `V` of Rprop. The step size is 
+/// adapted to the sign of the gradient `g` against the previous gradient 
+/// `pg`, and the weight takes a step against the sign of `g`. `change` is the 
+/// last change to the weight, which is reverted when backtracking. Returns 
+/// the gradient to remember for the next update, which is 0 when it is 
+/// forgotten.
+///
+#[inline]
+pub fn update<V, X>(
+  w: &mut f64, 
+  step: &mut f64, 
+  change: &mut f64, 
+  g: f64, 
+  pg: f64, 
+  increased: bool
+) -> f64
+  where V : RpropVariant,
+        X : RpropParameters
+{
+  let sign = g * pg;
+
+  if sign > 0f64 {
+    *step = (*step * X::increase()).min(X::max_step());
+  } else if sign < 0f64 {
+    *step = (*step * X::decrease()).max(X::min_step());
+
+    if V::backtrack(increased) { *w -= *change; }
+
+    if V::skip() {
+      *change = 0f64;
+      return 0f64
+    }
+  }
+
+  *change = if g > 0f64 { -*step } else if g < 0f64 { *step } else { 0f64 };
+  *w += *change;
+
+  g
+}
+
+
+/// Resilient backpropagation (RPROP) trainer where the stopping condition is 
+/// based on a max number of epochs. Yields the epoch, and the mean squared 
+/// error over the training set before the weights were updated.
+///
+/// The gradient is accumulated over the whole training set, and each weight 
+/// takes a step against the sign of its gradient. The size of each step 
+/// grows while the sign is unchanged, and shrinks when it changes. 
+/// `LearningRate` and `MomentumConstant` aren't used.
+///
+pub struct RpropTrainer<'a, N : 'a, T : 'a, V, X, Y> {
+  nnet: &'a mut N,
+  tset: &'a [T],
+  state: TrainerState,
+  steps: Vec<f64>,
+  changes: Vec<f64>,
+  error: f64,
+  epoch: usize,
+  max_epochs: usize,
+  vtype: PhantomData<V>,
+  tptype: PhantomData<X>,
+  nptype: PhantomData<Y>
+}
+
+/// Rprop+ trainer.
+///
+pub type RpropPlusTrainer<'a, N, T, X, Y> = RpropTrainer<'a, N, T, RpropPlus, X, Y>;
+
+/// Rprop- trainer.
+///
+pub type RpropMinusTrainer<'a, N, T, X, Y> = RpropTrainer<'a, N, T, RpropMinus, X, Y>;
+
+/// iRprop+ trainer.
+///
+pub type IRpropPlusTrainer<'a, N, T, X, Y> = RpropTrainer<'a, N, T, IRpropPlus, X, Y>;
+
+/// iRprop- trainer.
+///
+pub type IRpropMinusTrainer<'a, N, T, X, Y> = RpropTrainer<'a, N, T, IRpropMinus, X, Y>;
+
+impl<'a, N, T, V, X, Y> RpropTrainer<'a, N, T, V, X, Y> 
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        V : RpropVariant,
+        X : RpropParameters, 
+        Y : NeuralNetParameters
+{
+  /// Creates a new trainer for a neural net, given a training set, where the 
+  /// max number of epochs is set to `::std::usize::MAX`.
+  ///
+  #[inline(always)]
+  pub fn new(nnet: &'a mut N, tset: &'a [T]) -> Self {
+    Self::with_epochs(nnet, tset, ::std::usize::MAX)
+  }
+
+  /// Creates a new trainer for a neural net, given a training set, where the 
+  /// stopping condition is the number of epochs.
+  ///
+  #[inline(always)]
+  pub fn with_epochs(nnet: &'a mut N, tset: &'a [T], epochs: usize) -> Self {
+    let count = util::weight_count::<Y, N>();
+
+    RpropTrainer {
+      nnet: nnet,
+      tset: tset,
+      state: TrainerState::new::<_, N>(),
+      steps: vec![X::initial_step(); count],
+      changes: vec![0f64; count],
+      error: ::std::f64::INFINITY,
+      epoch: 0,
+      max_epochs: epochs,
+      vtype: PhantomData,
+      tptype: PhantomData,
+      nptype: PhantomData
+    }
+  }
+}
+
+impl<'a, N, T, V, X, Y> NeuralNetTrainer for RpropTrainer<'a, N, T, V, X, Y> 
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        V : RpropVariant,
+        X : RpropParameters, 
+        Y : NeuralNetParameters
+{ }
+
+impl<'a, N, T, V, X, Y> Iterator for RpropTrainer<'a, N, T, V, X, Y> 
+  where N : NeuralNet<Y>, 
+        T : TrainingSetMember, 
+        V : RpropVariant,
+        X : RpropParameters, 
+        Y : NeuralNetParameters
+{
+  type Item = (usize, f64);
+
+  fn next(&mut self) -> Option<(usize, f64)> {
+    if self.epoch == self.max_epochs {
+      return None
+    }
+
+    let epoch = self.epoch;
+
+    self.state.shift_gradient();
+
+    let err = self.tset.iter().fold(0f64, |acc, member| {
+      acc + util::accumulate_gradient(self.nnet, &mut self.state, member)
+    }) / self.tset.len() as f64;
+    let increased = err > self.error;
+    let mut ws = util::weights::<Y, _>(self.nnet);
+
+    for k in 0..ws.len() {
+      let g = self.state.gradient()[k];
+      let pg = self.state.pgradient()[k];
+
+      self.state.gradient_mut()[k] = update::<V, X>(
+        &mut ws[k], 
+        &mut self.steps[k], 
+        &mut self.changes[k], 
+        g, 
+        pg, 
+        increased);
+    }
+
+    util::set_weights(self.nnet, &ws);
+
+    self.error = err;
+    self.epoch += 1;
+
+    Some((epoch, err))
+  }
+}
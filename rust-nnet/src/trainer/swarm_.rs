@@ -0,0 +1,197 @@
+use std::marker::PhantomData;
+
+use num_cpus;
+use params::MSEFunction;
+use prelude::*;
+use rand::{thread_rng, Rng};
+use rand::distributions::IndependentSample;
+use rand::distributions::range::Range;
+use scoped_threadpool::Pool;
+use trainer::fitness_::ErrorFitness;
+use trainer::util;
+
+
+/// Particle swarm optimization trainer, where the stopping condition is the 
+/// number of iterations. Yields the best fitness found so far after each 
+/// iteration.
+///
+/// Each particle is the flattened weight vector of a neural net. The 
+/// fitness of the swarm is computed in parallel. The weights of the best 
+/// position found so far are copied into the trained neural net.
+///
+pub struct SwarmTrainer<'a, N : 'a, F, X, Y> {
+  nnet: &'a mut N,
+  fitness: F,
+  particles: Vec<N>,
+  positions: Vec<Vec<f64>>,
+  velocities: Vec<Vec<f64>>,
+  pbest: Vec<Vec<f64>>,
+  pscores: Vec<f64>,
+  best: f64,
+  pool: Pool,
+  epoch: usize,
+  max_epochs: usize,
+  tptype: PhantomData<X>,
+  nptype: PhantomData<Y>
+}
+
+impl<'a, N, T, X, Y> SwarmTrainer<'a, N, ErrorFitness<'a, T, MSEFunction, Y>, X, Y>
+  where N : NeuralNet<Y> + Clone,
+        T : TrainingSetMember,
+        X : SwarmParameters,
+        Y : NeuralNetParameters
+{
+  /// Creates a new trainer for a neural net, given a training set, where the 
+  /// max number of epochs is set to `::std::usize::MAX`. The fitness is the 
+  /// negative mean squared error over the training set.
+  ///
+  #[inline(always)]
+  pub fn new(nnet: &'a mut N, tset: &'a [T]) -> Self {
+    Self::with_epochs(nnet, tset, ::std::usize::MAX)
+  }
+
+  /// Creates a new trainer for a neural net, given a training set, where the 
+  /// stopping condition is the number of epochs. The fitness is the negative 
+  /// mean squared error over the training set.
+  ///
+  #[inline(always)]
+  pub fn with_epochs(nnet: &'a mut N, tset: &'a [T], epochs: usize) -> Self {
+    Self::with_fitness(nnet, ErrorFitness::new(tset), epochs)
+  }
+}
+
+impl<'a, N, F, X, Y> SwarmTrainer<'a, N, F, X, Y>
+  where N : NeuralNet<Y> + Clone,
+        F : Fitness<N>,
+        X : SwarmParameters,
+        Y : NeuralNetParameters
+{
+  /// Creates a new trainer for a neural net, given a fitness function, where 
+  /// the stopping condition is the number of epochs. The swarm starts with 
+  /// the weights of the neural net, and random positions.
+  ///
+  /// # Panics
+  ///
+  /// When the swarm is empty.
+  ///
+  pub fn with_fitness(nnet: &'a mut N, fitness: F, epochs: usize) -> Self {
+    if X::particles() == 0 {
+      panic!("swarm should have at least one particle")
+    }
+
+    let mut rng = thread_rng();
+    let vrange = Range::new(-X::max_velocity(), X::max_velocity());
+    let count = util::weight_count::<Y, N>();
+    let positions: Vec<Vec<f64>> = (0..X::particles())
+      .map(|i| {
+        if i == 0 {
+          util::weights::<Y, _>(nnet)
+        } else {
+          (0..count)
+            .map(|_| Y::WeightFunction::initw(N::dim_input(), N::dim_output()))
+            .collect()
+        }
+      })
+      .collect();
+    let velocities = (0..X::particles())
+      .map(|_| (0..count).map(|_| vrange.ind_sample(&mut rng)).collect())
+      .collect();
+
+    SwarmTrainer {
+      particles: (0..X::particles()).map(|_| nnet.clone()).collect(),
+      pbest: positions.clone(),
+      pscores: vec![::std::f64::NEG_INFINITY; X::particles()],
+      positions: positions,
+      velocities: velocities,
+      nnet: nnet,
+      fitness: fitness,
+      best: ::std::f64::NEG_INFINITY,
+      pool: Pool::new(num_cpus::get() as u32),
+      epoch: 0,
+      max_epochs: epochs,
+      tptype: PhantomData,
+      nptype: PhantomData
+    }
+  }
+
+  /// Returns the particle with the best personal best position in the 
+  /// neighbourhood of a particle.
+  ///
+  fn neighbourhood_best(&self, i: usize) -> usize {
+    let n = self.pscores.len();
+    let better = |acc: usize, j: usize| {
+      if self.pscores[j] > self.pscores[acc] { j } else { acc }
+    };
+
+    match X::topology() {
+      Topology::Ring(k) if 2 * k + 1 < n => {
+        (1..k + 1).fold(i, |acc, d| better(better(acc, (i + d) % n), (i + n - d) % n))
+      }
+      _ => (0..n).fold(i, &better)
+    }
+  }
+}
+
+impl<'a, N, F, X, Y> NeuralNetTrainer for SwarmTrainer<'a, N, F, X, Y>
+  where N : NeuralNet<Y> + Clone + Send,
+        F : Fitness<N> + Sync,
+        X : SwarmParameters,
+        Y : NeuralNetParameters
+{ }
+
+impl<'a, N, F, X, Y> Iterator for SwarmTrainer<'a, N, F, X, Y>
+  where N : NeuralNet<Y> + Clone + Send,
+        F : Fitness<N> + Sync,
+        X : SwarmParameters,
+        Y : NeuralNetParameters
+{
+  type Item = (usize, f64);
+
+  fn next(&mut self) -> Option<(usize, f64)> {
+    if self.epoch == self.max_epochs {
+      return None
+    }
+
+    let epoch = self.epoch;
+    let mut rng = thread_rng();
+
+    for (nn, x) in self.particles.iter_mut().zip(self.positions.iter()) {
+      util::set_weights(nn, x);
+    }
+
+    let scores = util::evaluate(&mut self.pool, &self.fitness, &mut self.particles);
+
+    for i in 0..scores.len() {
+      if scores[i] > self.pscores[i] {
+        self.pscores[i] = scores[i];
+        self.pbest[i].clone_from(&self.positions[i]);
+      }
+
+      if scores[i] > self.best {
+        self.best = scores[i];
+        util::set_weights(self.nnet, &self.positions[i]);
+      }
+    }
+
+    let vmax = X::max_velocity();
+
+    for i in 0..self.positions.len() {
+      let l = self.neighbourhood_best(i);
+
+      for d in 0..self.positions[i].len() {
+        let x = self.positions[i][d];
+        let v = X::inertia() * self.velocities[i][d] + 
+          X::cognitive() * rng.gen::<f64>() * (self.pbest[i][d] - x) + 
+          X::social() * rng.gen::<f64>() * (self.pbest[l][d] - x);
+        let v = v.max(-vmax).min(vmax);
+
+        self.velocities[i][d] = v;
+        self.positions[i][d] = x + v;
+      }
+    }
+
+    self.epoch += 1;
+
+    Some((epoch, self.best))
+  }
+}
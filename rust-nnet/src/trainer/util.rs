@@ -1,4 +1,8 @@
 use prelude::*;
+use rand::{thread_rng, Rng};
+use rand::distributions::IndependentSample;
+use rand::distributions::normal::Normal;
+use scoped_threadpool::Pool;
 
 
 #[derive(Clone, Debug)]
@@ -7,6 +11,10 @@ pub struct TrainerState {
   doutput: Vec<Vec<f64>>, 
   ehidden: Vec<f64>,
   eoutput: Vec<f64>,
+  ninput: Vec<f64>,
+  ntarget: Vec<f64>,
+  gradient: Vec<f64>,
+  pgradient: Vec<f64>
 } 
 
 impl TrainerState {
@@ -17,7 +25,11 @@ impl TrainerState {
       dinput: Vec::with_capacity(N::dim_input() + 1),
       doutput: Vec::with_capacity(N::dim_hidden() + 1),
       ehidden: Vec::with_capacity(N::dim_hidden() + 1),
-      eoutput: Vec::with_capacity(N::dim_output() + 1)
+      eoutput: Vec::with_capacity(N::dim_output() + 1),
+      ninput: Vec::with_capacity(N::dim_input()),
+      ntarget: Vec::with_capacity(N::dim_output()),
+      gradient: vec![0f64; weight_count::<P, N>()],
+      pgradient: vec![0f64; weight_count::<P, N>()]
     };
 
     for _ in (0..N::dim_input() + 1) {
@@ -36,6 +48,28 @@ impl TrainerState {
     state
   }
 
+  /// Returns the gradient accumulated by `accumulate_gradient`.
+  ///
+  #[inline(always)] pub fn gradient(&self) -> &[f64] { &self.gradient }
+
+  /// Returns a mutable reference to the accumulated gradient.
+  ///
+  #[inline(always)] pub fn gradient_mut(&mut self) -> &mut [f64] { &mut self.gradient }
+
+  /// Returns the gradient accumulated before the last call to 
+  /// `shift_gradient`.
+  ///
+  #[inline(always)] pub fn pgradient(&self) -> &[f64] { &self.pgradient }
+
+  /// Moves the accumulated gradient into the previous gradient, and clears 
+  /// the accumulated gradient.
+  ///
+  pub fn shift_gradient(&mut self) {
+    ::std::mem::swap(&mut self.gradient, &mut self.pgradient);
+
+    for g in self.gradient.iter_mut() { *g = 0f64; }
+  }
+
   pub fn combine<I>(&mut self, states: I) 
     where I : Iterator<Item = TrainerState> 
   {
@@ -72,6 +106,47 @@ impl TrainerState {
 }
 
 
+/// Copies the input and expected result of a member into the state, and 
+/// applies noise to the copies. Returns false, without copying, if there is 
+/// no noise to apply.
+///
+fn corrupt<Z, M>(state: &mut TrainerState, member: &M) -> bool
+  where Z : Noise,
+        M : TrainingSetMember
+{
+  let isd = Z::input_stddev();
+  let mask = Z::mask_probability();
+  let tsd = Z::target_stddev();
+
+  if isd <= 0f64 && mask <= 0f64 && tsd <= 0f64 { return false }
+
+  let mut rng = thread_rng();
+
+  state.ninput.clear();
+  state.ninput.extend(member.input().iter().cloned());
+  state.ntarget.clear();
+  state.ntarget.extend(member.expected().iter().cloned());
+
+  if isd > 0f64 {
+    let normal = Normal::new(0f64, isd);
+    for x in state.ninput.iter_mut() { *x += normal.ind_sample(&mut rng); }
+  }
+
+  if mask > 0f64 {
+    for x in state.ninput.iter_mut() { 
+      if rng.gen::<f64>() < mask { *x = 0f64; } 
+    }
+  }
+
+  if tsd > 0f64 {
+    let normal = Normal::new(0f64, tsd);
+    for x in state.ntarget.iter_mut() { *x += normal.ind_sample(&mut rng); }
+  }
+
+  true
+}
+
+
 /// Compares a neural network's prediction for an input, and calculates the 
 /// error given an expected result. Updates the state with the deltas and errors 
 /// of the hidden and output layers.
@@ -82,17 +157,91 @@ pub fn update_state<X, Y, N, M>(nn: &mut N, state: &mut TrainerState, member: &M
         N : NeuralNet<Y>, 
         M : TrainingSetMember
 {
-  let exp = member.expected();
-  
   nn.predict(member.input());
+  update_output::<X, Y, N>(nn, state, member.expected());
+}
 
-  let res = nn.layer(Layer::Output);
-  let inp = nn.layer(Layer::Input);
 
-  for i in (0..N::dim_output()) {
+/// Like `update_state`, but noise (see `Noise`) is applied to a copy of the 
+/// member before the prediction is made.
+///
+pub fn update_state_noisy<X, Y, N, M>(nn: &mut N, state: &mut TrainerState, member: &M)
+  where X : DenoisingParameters,
+        Y : NeuralNetParameters,
+        N : NeuralNet<Y>, 
+        M : TrainingSetMember
+{
+  if !corrupt::<X::Noise, _>(state, member) {
+    return update_state::<X, Y, N, M>(nn, state, member)
+  }
+
+  nn.predict(&state.ninput);
+
+  let exp = ::std::mem::replace(&mut state.ntarget, Vec::new());
+
+  update_output::<X, Y, N>(nn, state, &exp);
+  state.ntarget = exp;
+}
+
+
+/// Updates the state with the errors of the output layer, given an expected 
+/// result, and propagates them back through the neural net. `predict` should 
+/// be called on the neural net beforehand.
+///
+fn update_output<X, Y, N>(nn: &N, state: &mut TrainerState, exp: &[f64])
+  where X : TrainerParameters,
+        Y : NeuralNetParameters,
+        N : NeuralNet<Y>
+{
+  {
+    let res = nn.layer(Layer::Output);
+
+    for i in 0..N::dim_output() {
+      state.eoutput[i] = 
+        X::ErrorGradient::erroutput::<Y::ActivationFunction>(exp[i], res[i]);
+    }
+  }
+
+  propagate::<X, Y, N>(nn, state);
+}
+
+
+/// Updates the state with the deltas and errors of the hidden and output 
+/// layers, given errors injected at the output layer instead of an expected 
+/// result. `errors[i]` is the direction, and amount, output node `i` should 
+/// move in (analogous to `expected - actual`). `predict` should be called 
+/// on the neural net beforehand.
+///
+pub fn update_state_with_errors<X, Y, N>(
+  nn: &N, 
+  state: &mut TrainerState, 
+  errors: &[f64]
+)
+  where X : TrainerParameters,
+        Y : NeuralNetParameters,
+        N : NeuralNet<Y>
+{
+  for i in 0..N::dim_output() {
     state.eoutput[i] = 
-      X::ErrorGradient::erroutput::<Y::ActivationFunction>(exp[i], res[i]);
+      Y::ActivationFunction::derivative_from_output(nn.node(Node::Output(i))) * errors[i];
+  }
+
+  propagate::<X, Y, N>(nn, state);
+}
 
+
+/// Propagates the errors of the output layer in the state back through the 
+/// neural net, and updates the deltas and errors of the hidden and output 
+/// layers.
+///
+fn propagate<X, Y, N>(nn: &N, state: &mut TrainerState)
+  where X : TrainerParameters,
+        Y : NeuralNetParameters,
+        N : NeuralNet<Y>
+{
+  let inp = nn.layer(Layer::Input);
+
+  for i in (0..N::dim_output()) {
     for j in (0..N::dim_hidden() + 1) {
       state.doutput[j][i] = X::LearningRate::lrate() * 
         nn.node(Node::Hidden(j)) * state.eoutput[i] + 
@@ -119,6 +268,106 @@ pub fn update_state<X, Y, N, M>(nn: &mut N, state: &mut TrainerState, member: &M
 }
 
 
+/// Predicts the input of a member with a neural network, and adds the 
+/// gradient of the squared error (halved) with respect to each weight to the 
+/// accumulated gradient in the state, in the order returned by `weights`. 
+/// Returns the mean squared error of the prediction.
+///
+pub fn accumulate_gradient<Y, N, M>(nn: &mut N, state: &mut TrainerState, member: &M) -> f64
+  where Y : NeuralNetParameters,
+        N : NeuralNet<Y>,
+        M : TrainingSetMember
+{
+  let exp = member.expected();
+  let mut err = 0f64;
+
+  nn.predict(member.input());
+
+  for i in 0..N::dim_output() {
+    let act = nn.node(Node::Output(i));
+
+    err += (exp[i] - act) * (exp[i] - act);
+    state.eoutput[i] = 
+      (exp[i] - act) * Y::ActivationFunction::derivative_from_output(act);
+  }
+
+  for i in 0..N::dim_hidden() {
+    let wsum = (0..N::dim_output()).fold(
+      0f64, 
+      |acc, j| acc + (nn.node(Node::WeightHiddenOutput(i, j)) * state.eoutput[j]));
+
+    state.ehidden[i] = 
+      Y::ActivationFunction::derivative_from_output(nn.node(Node::Hidden(i))) * wsum;
+  }
+
+  let mut k = 0;
+
+  for i in 0..N::dim_input() + 1 {
+    for j in 0..N::dim_hidden() {
+      state.gradient[k] -= nn.node(Node::Input(i)) * state.ehidden[j];
+      k += 1;
+    }
+  }
+
+  for i in 0..N::dim_hidden() + 1 {
+    for j in 0..N::dim_output() {
+      state.gradient[k] -= nn.node(Node::Hidden(i)) * state.eoutput[j];
+      k += 1;
+    }
+  }
+
+  err / N::dim_output() as f64
+}
+
+
+/// Replaces the accumulated gradient in the state with the gradient over a 
+/// whole training set. Returns half the summed squared error, whose gradient 
+/// it is.
+///
+pub fn batch_gradient<Y, N, T>(nn: &mut N, state: &mut TrainerState, tset: &[T]) -> f64
+  where Y : NeuralNetParameters,
+        N : NeuralNet<Y>,
+        T : TrainingSetMember
+{
+  for g in state.gradient.iter_mut() { *g = 0f64; }
+
+  let err = tset.iter().fold(0f64, |acc, member| {
+    acc + accumulate_gradient(nn, state, member)
+  });
+
+  err * N::dim_output() as f64 / 2f64
+}
+
+
+/// Computes the derivative of an output node of a neural network with 
+/// respect to each weight, in the order returned by `weights`. `predict` 
+/// should be called on the neural net beforehand.
+///
+pub fn output_gradient<Y, N>(nn: &N, output: usize, row: &mut [f64])
+  where Y : NeuralNetParameters,
+        N : NeuralNet<Y>
+{
+  let delta = Y::ActivationFunction::derivative_from_output(nn.node(Node::Output(output)));
+  let mut k = 0;
+
+  for i in 0..N::dim_input() + 1 {
+    for j in 0..N::dim_hidden() {
+      row[k] = delta * nn.node(Node::WeightHiddenOutput(j, output)) * 
+        Y::ActivationFunction::derivative_from_output(nn.node(Node::Hidden(j))) * 
+        nn.node(Node::Input(i));
+      k += 1;
+    }
+  }
+
+  for i in 0..N::dim_hidden() + 1 {
+    for j in 0..N::dim_output() {
+      row[k] = if j == output { delta * nn.node(Node::Hidden(i)) } else { 0f64 };
+      k += 1;
+    }
+  }
+}
+
+
 /// Update weights in each layer of a neural network with a single hidden layer.
 ///
 pub fn update_weights<P, N>(nn: &mut N, state: &TrainerState) 
@@ -138,4 +387,104 @@ pub fn update_weights<P, N>(nn: &mut N, state: &TrainerState)
       *nn.node_mut(Node::WeightHiddenOutput(i, j)) = w + state.doutput[i][j];
     }
   }
-}
\ No newline at end of file
+}
+
+
+/// Scales every weight in each layer of a neural network by `factor`.
+///
+pub fn scale_weights<P, N>(nn: &mut N, factor: f64)
+  where N : NeuralNet<P>,
+        P : NeuralNetParameters
+{
+  for i in 0..N::dim_input() + 1 {
+    for j in 0..N::dim_hidden() {
+      *nn.node_mut(Node::WeightInputHidden(i, j)) *= factor;
+    }
+  }
+
+  for i in 0..N::dim_hidden() + 1 {
+    for j in 0..N::dim_output() {
+      *nn.node_mut(Node::WeightHiddenOutput(i, j)) *= factor;
+    }
+  }
+}
+
+/// Returns the number of weights in a neural network with a single hidden 
+/// layer.
+///
+#[inline]
+pub fn weight_count<P, N>() -> usize
+  where N : NeuralNet<P>,
+        P : NeuralNetParameters
+{
+  (N::dim_input() + 1) * N::dim_hidden() + (N::dim_hidden() + 1) * N::dim_output()
+}
+
+
+/// Copies every weight in each layer of a neural network into a flat vector. 
+/// Weights from the input layer come first, followed by the weights from the 
+/// hidden layer.
+///
+pub fn weights<P, N>(nn: &N) -> Vec<f64>
+  where N : NeuralNet<P>,
+        P : NeuralNetParameters
+{
+  let mut ws = Vec::with_capacity(weight_count::<P, N>());
+
+  for i in 0..N::dim_input() + 1 {
+    for j in 0..N::dim_hidden() {
+      ws.push(nn.node(Node::WeightInputHidden(i, j)));
+    }
+  }
+
+  for i in 0..N::dim_hidden() + 1 {
+    for j in 0..N::dim_output() {
+      ws.push(nn.node(Node::WeightHiddenOutput(i, j)));
+    }
+  }
+
+  ws
+}
+
+
+/// Sets every weight in each layer of a neural network from a flat vector, 
+/// in the order returned by `weights`.
+///
+pub fn set_weights<P, N>(nn: &mut N, ws: &[f64])
+  where N : NeuralNet<P>,
+        P : NeuralNetParameters
+{
+  let mut k = 0;
+
+  for i in 0..N::dim_input() + 1 {
+    for j in 0..N::dim_hidden() {
+      *nn.node_mut(Node::WeightInputHidden(i, j)) = ws[k];
+      k += 1;
+    }
+  }
+
+  for i in 0..N::dim_hidden() + 1 {
+    for j in 0..N::dim_output() {
+      *nn.node_mut(Node::WeightHiddenOutput(i, j)) = ws[k];
+      k += 1;
+    }
+  }
+}
+
+
+/// Computes the fitness of each neural net on a thread pool.
+///
+pub fn evaluate<N, F>(pool: &mut Pool, fitness: &F, nets: &mut [N]) -> Vec<f64>
+  where N : Send,
+        F : Fitness<N> + Sync
+{
+  let mut scores = vec![0f64; nets.len()];
+
+  pool.scoped(|scope| {
+    for (nn, score) in nets.iter_mut().zip(scores.iter_mut()) {
+      scope.execute(move || *score = fitness.fitness(nn));
+    }
+  });
+
+  scores
+}
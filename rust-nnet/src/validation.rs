@@ -0,0 +1,402 @@
+use rand::{Rng, SeedableRng, StdRng};
+use prelude::*;
+
+
+/// Returns the index of the largest value.
+///
+/// # Panics
+///
+/// When `v` is empty.
+///
+pub fn argmax(v: &[f64]) -> usize {
+  if v.is_empty() { panic!("there should be at least 1 value") }
+
+  let mut max = 0;
+
+  for i in 1..v.len() {
+    if v[i] > v[max] { max = i; }
+  }
+
+  max
+}
+
+
+/// Returns the threshold between the two classes of a single output, which 
+/// is the midpoint of the bounds of the activation function `A`.
+///
+#[inline]
+pub fn threshold<A>() -> f64 where A : BoundedActivationFunction {
+  let (lo, hi) = A::bounds();
+  (lo + hi) / 2f64
+}
+
+
+/// Returns the class of an expected result, or of a prediction. For a single 
+/// output, the class is 1 if the value is at least `threshold`, and 0 
+/// otherwise (see `threshold`). For multiple outputs, the class is the index 
+/// of the largest value.
+///
+pub fn class_of(v: &[f64], threshold: f64) -> usize {
+  if v.len() == 1 {
+    if v[0] >= threshold { 1 } else { 0 }
+  } else {
+    argmax(v)
+  }
+}
+
+
+/// Partitions the indices of a training set into `k` folds of (nearly) equal 
+/// size, after shuffling them with `seed`.
+///
+/// # Panics
+///
+/// When `k` is less than 2.
+///
+pub fn folds<T>(tset: &[T], k: usize, seed: usize) -> Vec<Vec<usize>> {
+  if k < 2 { panic!("there should be at least 2 folds") }
+
+  let mut rng: StdRng = SeedableRng::from_seed(&[seed][..]);
+  let mut indices: Vec<usize> = (0..tset.len()).collect();
+
+  rng.shuffle(&mut indices);
+
+  deal(indices.into_iter(), k)
+}
+
+
+/// Partitions the indices of a training set into `k` folds of (nearly) equal 
+/// size, where each fold has (nearly) the same proportion of each class as 
+/// the training set. The class of a member is found with `class_of` on its 
+/// expected result, using `threshold`. Members within a class are shuffled 
+/// with `seed`.
+///
+/// # Panics
+///
+/// When `k` is less than 2.
+///
+pub fn stratified_folds<T>(
+  tset: &[T], 
+  k: usize, 
+  threshold: f64, 
+  seed: usize
+) -> Vec<Vec<usize>>
+  where T : TrainingSetMember
+{
+  if k < 2 { panic!("there should be at least 2 folds") }
+
+  deal(classes(tset, threshold, seed).into_iter().flat_map(|class| class.into_iter()), k)
+}
+
+
+/// Groups the indices of a training set by the class of their expected 
+/// result (using `threshold`), and shuffles each group with `seed`.
+///
+fn classes<T>(tset: &[T], threshold: f64, seed: usize) -> Vec<Vec<usize>> 
+  where T : TrainingSetMember 
+{
+  let mut rng: StdRng = SeedableRng::from_seed(&[seed][..]);
+  let mut classes: Vec<Vec<usize>> = Vec::new();
+
+  for (i, member) in tset.iter().enumerate() {
+    let class = class_of(member.expected(), threshold);
+
+    while classes.len() <= class { classes.push(Vec::new()); }
+
+    classes[class].push(i);
+  }
+
+  for class in classes.iter_mut() { rng.shuffle(class); }
+
+  classes
+}
+
+
+/// Deals indices out to `k` folds, one at a time.
+///
+fn deal<I>(indices: I, k: usize) -> Vec<Vec<usize>> where I : Iterator<Item = usize> {
+  let mut folds: Vec<Vec<usize>> = (0..k).map(|_| Vec::new()).collect();
+
+  for (i, j) in indices.enumerate() { folds[i % k].push(j); }
+
+  for fold in folds.iter_mut() { fold.sort(); }
+
+  folds
+}
+
+
+/// A split of a training set into training, validation, and test members. 
+/// The members aren't copied; each part is a list of indices into the 
+/// training set, in order, and can be viewed as a list of references, which 
+/// is accepted by every trainer.
+///
+#[derive(Clone, Debug)]
+pub struct Split<'a, T : 'a> {
+  tset: &'a [T],
+  train: Vec<usize>,
+  validation: Vec<usize>,
+  test: Vec<usize>
+}
+
+impl<'a, T> Split<'a, T> {
+  /// Splits a training set at random, after shuffling it with `seed`. 
+  /// `validation` and `test` are the fractions of the training set to hold 
+  /// out in each, and the rest is used for training.
+  ///
+  /// # Panics
+  ///
+  /// When either fraction is negative, or their sum is greater than 1.
+  ///
+  pub fn random(tset: &'a [T], validation: f64, test: f64, seed: usize) -> Split<'a, T> {
+    let mut rng: StdRng = SeedableRng::from_seed(&[seed][..]);
+    let mut indices: Vec<usize> = (0..tset.len()).collect();
+
+    rng.shuffle(&mut indices);
+
+    let mut split = Split::empty(tset);
+    split.extend(indices, validation, test);
+    split.sort();
+    split
+  }
+
+  /// Splits a training set where members are ordered by time, so every 
+  /// member used for training comes before every member used for 
+  /// validation, which come before every member used for testing. 
+  /// `validation` and `test` are the fractions of the training set to hold 
+  /// out in each.
+  ///
+  /// # Panics
+  ///
+  /// When either fraction is negative, or their sum is greater than 1.
+  ///
+  pub fn time_ordered(tset: &'a [T], validation: f64, test: f64) -> Split<'a, T> {
+    let mut split = Split::empty(tset);
+    split.extend((0..tset.len()).collect(), validation, test);
+    split
+  }
+
+  /// Splits a training set so each part has (nearly) the same proportion of 
+  /// each class as the training set. The class of a member is found with 
+  /// `class_of` on its expected result, using `threshold`. Members within a 
+  /// class are shuffled with `seed`. `validation` and `test` are the 
+  /// fractions of each class to hold out in each.
+  ///
+  /// # Panics
+  ///
+  /// When either fraction is negative, or their sum is greater than 1.
+  ///
+  pub fn stratified(
+    tset: &'a [T], 
+    validation: f64, 
+    test: f64, 
+    threshold: f64, 
+    seed: usize
+  ) -> Split<'a, T> 
+    where T : TrainingSetMember 
+  {
+    let mut split = Split::empty(tset);
+
+    for class in classes(tset, threshold, seed).into_iter() {
+      split.extend(class, validation, test);
+    }
+
+    split.sort();
+    split
+  }
+
+  #[inline(always)]
+  fn empty(tset: &'a [T]) -> Split<'a, T> {
+    Split { tset: tset, train: Vec::new(), validation: Vec::new(), test: Vec::new() }
+  }
+
+  /// Divides indices between each part. The last are used for testing, the 
+  /// ones before them for validation, and the rest for training.
+  ///
+  fn extend(&mut self, indices: Vec<usize>, validation: f64, test: f64) {
+    if validation < 0f64 || test < 0f64 || validation + test > 1f64 {
+      panic!("fractions should be non-negative, and sum to at most 1")
+    }
+
+    let len = indices.len();
+    let ntest = ::std::cmp::min((test * len as f64).round() as usize, len);
+    let nvalidation = ::std::cmp::min((validation * len as f64).round() as usize, len - ntest);
+    let ntrain = len - ntest - nvalidation;
+
+    self.train.extend(indices[..ntrain].iter().cloned());
+    self.validation.extend(indices[ntrain..ntrain + nvalidation].iter().cloned());
+    self.test.extend(indices[ntrain + nvalidation..].iter().cloned());
+  }
+
+  fn sort(&mut self) {
+    self.train.sort();
+    self.validation.sort();
+    self.test.sort();
+  }
+
+  /// Returns the indices of the members used for training.
+  ///
+  #[inline(always)] pub fn train_indices(&self) -> &[usize] { &self.train }
+
+  /// Returns the indices of the members used for validation.
+  ///
+  #[inline(always)] pub fn validation_indices(&self) -> &[usize] { &self.validation }
+
+  /// Returns the indices of the members used for testing.
+  ///
+  #[inline(always)] pub fn test_indices(&self) -> &[usize] { &self.test }
+
+  /// Returns the members used for training.
+  ///
+  #[inline] pub fn train(&self) -> Vec<&'a T> { self.view(&self.train) }
+
+  /// Returns the members used for validation.
+  ///
+  #[inline] pub fn validation(&self) -> Vec<&'a T> { self.view(&self.validation) }
+
+  /// Returns the members used for testing.
+  ///
+  #[inline] pub fn test(&self) -> Vec<&'a T> { self.view(&self.test) }
+
+  #[inline]
+  fn view(&self, indices: &[usize]) -> Vec<&'a T> {
+    let tset = self.tset;
+    indices.iter().map(|&i| &tset[i]).collect()
+  }
+}
+
+
+/// Metrics of a neural net, computed over the held out fold.
+///
+#[derive(Copy, Clone, Debug)]
+pub struct FoldMetrics {
+  /// Average error, using the error function passed to `cross_validate`.
+  ///
+  pub error: f64,
+
+  /// Fraction of members whose predicted class matches their expected 
+  /// class (see `class_of`). Single outputs are split at the midpoint of the 
+  /// bounds of the activation function.
+  ///
+  pub accuracy: f64
+}
+
+
+/// Results of cross-validation.
+///
+#[derive(Clone, Debug)]
+pub struct CrossValidation {
+  /// Metrics for each fold.
+  ///
+  pub folds: Vec<FoldMetrics>
+}
+
+impl CrossValidation {
+  /// Returns the average error over each fold.
+  ///
+  #[inline]
+  pub fn mean_error(&self) -> f64 { mean(self.folds.iter().map(|f| f.error)) }
+
+  /// Returns the standard deviation of the error over each fold.
+  ///
+  #[inline]
+  pub fn stddev_error(&self) -> f64 { stddev(self.folds.iter().map(|f| f.error)) }
+
+  /// Returns the average accuracy over each fold.
+  ///
+  #[inline]
+  pub fn mean_accuracy(&self) -> f64 { 
+    mean(self.folds.iter().map(|f| f.accuracy)) 
+  }
+
+  /// Returns the standard deviation of the accuracy over each fold.
+  ///
+  #[inline]
+  pub fn stddev_accuracy(&self) -> f64 { 
+    stddev(self.folds.iter().map(|f| f.accuracy)) 
+  }
+}
+
+
+fn mean<I>(xs: I) -> f64 where I : Iterator<Item = f64> {
+  let (n, sum) = xs.fold((0f64, 0f64), |(n, sum), x| (n + 1f64, sum + x));
+  if n == 0f64 { 0f64 } else { sum / n }
+}
+
+fn stddev<I>(xs: I) -> f64 where I : Iterator<Item = f64> + Clone {
+  let m = mean(xs.clone());
+  mean(xs.map(|x| (x - m).powi(2))).sqrt()
+}
+
+
+/// Runs k-fold cross-validation on a training set. For each fold, a new 
+/// neural net is created with `new`, and trained on the remaining folds with 
+/// `train`, which is expected to run a trainer to completion. The neural net 
+/// is then scored on the held out fold using the error function `E`. 
+///
+/// If `stratified` is true, folds are created with `stratified_folds`, and 
+/// otherwise with `folds`. Classes of single outputs are split at the 
+/// midpoint of the bounds of the activation function (see `threshold`).
+///
+/// # Panics
+///
+/// When `k` is less than 2.
+///
+pub fn cross_validate<E, Y, N, T, G, F>(
+  tset: &[T], 
+  k: usize, 
+  stratified: bool, 
+  seed: usize, 
+  mut new: G, 
+  mut train: F
+) -> CrossValidation
+  where E : ErrorFunction,
+        Y : NeuralNetParameters,
+        Y::ActivationFunction : BoundedActivationFunction,
+        N : NeuralNet<Y>,
+        T : TrainingSetMember,
+        G : FnMut() -> N,
+        F : FnMut(&mut N, &[&T])
+{
+  let threshold = threshold::<Y::ActivationFunction>();
+  let folds = if stratified { 
+    stratified_folds(tset, k, threshold, seed) 
+  } else { 
+    folds(tset, k, seed) 
+  };
+
+  let metrics = (0..k)
+    .map(|i| {
+      let training: Vec<&T> = folds
+        .iter()
+        .enumerate()
+        .filter(|&(j, _)| j != i)
+        .flat_map(|(_, fold)| fold.iter().map(|&m| &tset[m]))
+        .collect();
+
+      let mut nn = new();
+
+      train(&mut nn, &training);
+
+      let mut err = 0f64;
+      let mut correct = 0f64;
+
+      for &m in folds[i].iter() {
+        let exp = tset[m].expected();
+
+        nn.predict(tset[m].input());
+
+        let act = nn.layer(Layer::Output);
+
+        err += E::error(act.iter(), exp.iter());
+        if class_of(act, threshold) == class_of(exp, threshold) { 
+          correct += 1f64; 
+        }
+      }
+
+      let len = ::std::cmp::max(folds[i].len(), 1) as f64;
+
+      FoldMetrics { error: err / len, accuracy: correct / len }
+    })
+    .collect();
+
+  CrossValidation { folds: metrics }
+}
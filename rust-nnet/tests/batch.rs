@@ -0,0 +1,361 @@
+extern crate nnet;
+#[macro_use(ffnn)] extern crate nnet_macros;
+
+use nnet::params::{LogisticNeuralNet, TanhNeuralNet};
+use nnet::prelude::{NeuralNet, Predictor, Node, NeuralNetParameters,
+  RpropParameters, QuickpropParameters, LevenbergMarquardtParameters,
+  ConjugateGradientParameters, LbfgsParameters};
+use nnet::trainer::rprop::IRpropPlusTrainer;
+use nnet::trainer::quickprop::QuickpropTrainer;
+use nnet::trainer::levenberg_marquardt::LevenbergMarquardtTrainer;
+use nnet::trainer::conjugate_gradient::ScaledConjugateGradientTrainer;
+use nnet::trainer::lbfgs::LbfgsTrainer;
+
+
+ffnn!(TestNeuralNet, 3, 4, 2);
+ffnn!(SingleOutputNeuralNet, 3, 4, 1);
+
+
+struct TestParams;
+
+impl RpropParameters for TestParams { }
+
+impl QuickpropParameters for TestParams {
+  fn epsilon() -> f64 { 0.1f64 }
+  fn decay() -> f64 { 0f64 }
+}
+
+impl LevenbergMarquardtParameters for TestParams {
+  fn initial_damping() -> f64 { 1e6f64 }
+}
+
+impl ConjugateGradientParameters for TestParams {
+  fn lambda() -> f64 { 1e3f64 }
+}
+
+impl LbfgsParameters for TestParams { }
+
+
+struct DefaultParams;
+
+impl ConjugateGradientParameters for DefaultParams { }
+
+
+/// Training set whose expected results are near 0, so a tanh network can 
+/// fit it without saturating.
+///
+fn tset() -> Vec<(Vec<f64>, Vec<f64>)> {
+  vec![
+    (vec![0.3f64, -0.7f64, 0.5f64], vec![-0.25f64, 0.25f64]),
+    (vec![-0.4f64, 0.1f64, 0.9f64], vec![0.15f64, -0.15f64]),
+    (vec![0.8f64, 0.6f64, -0.2f64], vec![-0.05f64, 0.05f64])
+  ]
+}
+
+
+/// Number of weights in a neural net.
+///
+fn weight_count<P, N>() -> usize
+  where P : NeuralNetParameters,
+        N : NeuralNet<P>
+{
+  (N::dim_input() + 1) * N::dim_hidden() + (N::dim_hidden() + 1) * N::dim_output()
+}
+
+
+/// Returns weight `k` of a neural net, with the weights from the input
+/// layer first, followed by the weights from the hidden layer.
+///
+fn weight<P, N>(k: usize) -> Node
+  where P : NeuralNetParameters,
+        N : NeuralNet<P>
+{
+  let inputs = (N::dim_input() + 1) * N::dim_hidden();
+
+  if k < inputs {
+    Node::WeightInputHidden(k / N::dim_hidden(), k % N::dim_hidden())
+  } else {
+    Node::WeightHiddenOutput((k - inputs) / N::dim_output(), (k - inputs) % N::dim_output())
+  }
+}
+
+
+/// Half the squared error of a neural net, averaged over the training set.
+///
+fn error<P, N>(nn: &mut N, tset: &[(Vec<f64>, Vec<f64>)]) -> f64
+  where P : NeuralNetParameters,
+        N : NeuralNet<P>
+{
+  tset.iter().fold(0f64, |acc, &(ref inp, ref exp)| {
+    nn.predict(inp);
+
+    acc + nn.output().iter().zip(exp.iter()).fold(0f64, |acc, (act, exp)| {
+      acc + 0.5f64 * (exp - act) * (exp - act)
+    })
+  }) / tset.len() as f64
+}
+
+
+/// Central differences of `f` with respect to each weight of a neural net.
+///
+fn numeric_gradient<P, N, F>(nn: &mut N, f: F) -> Vec<f64>
+  where P : NeuralNetParameters,
+        N : NeuralNet<P>,
+        F : Fn(&mut N) -> f64
+{
+  let h = 1e-6f64;
+
+  (0..weight_count::<P, N>()).map(|k| {
+    let orig = nn.node(weight::<P, N>(k));
+
+    *nn.node_mut(weight::<P, N>(k)) = orig + h;
+    let fhi = f(nn);
+
+    *nn.node_mut(weight::<P, N>(k)) = orig - h;
+    let flo = f(nn);
+
+    *nn.node_mut(weight::<P, N>(k)) = orig;
+
+    (fhi - flo) / (2f64 * h)
+  }).collect()
+}
+
+
+/// Returns the change in each weight of a neural net after a single epoch 
+/// of a trainer.
+///
+fn first_step<P, N, F>(nn: &mut N, train: F) -> Vec<f64>
+  where P : NeuralNetParameters,
+        N : NeuralNet<P>,
+        F : FnOnce(&mut N)
+{
+  let before: Vec<f64> = (0..weight_count::<P, N>())
+    .map(|k| nn.node(weight::<P, N>(k)))
+    .collect();
+
+  train(nn);
+
+  (0..weight_count::<P, N>())
+    .map(|k| nn.node(weight::<P, N>(k)) - before[k])
+    .collect()
+}
+
+
+/// The first step of Quickprop is plain gradient descent, so it is
+/// `-epsilon` times the gradient of the averaged error.
+///
+fn check_quickprop<P>() where P : NeuralNetParameters {
+  let tset = tset();
+  let mut nn = TestNeuralNet::<P>::new();
+  let numeric = numeric_gradient(&mut nn, |nn| error(nn, &tset));
+  let step = first_step(&mut nn, |nn: &mut TestNeuralNet<P>| {
+    QuickpropTrainer::<_, _, TestParams, _>::with_epochs(nn, &tset, 1).last();
+  });
+
+  for (k, (s, g)) in step.iter().zip(numeric.iter()).enumerate() {
+    let analytic = -s / TestParams::epsilon();
+
+    assert!(
+      (analytic - g).abs() < 1e-6f64,
+      "weight {}: {} != {}", k, analytic, g);
+  }
+}
+
+
+/// The first step of iRprop+ moves each weight by the initial step size,
+/// against the sign of its gradient.
+///
+fn check_rprop<P>() where P : NeuralNetParameters {
+  let tset = tset();
+  let mut nn = TestNeuralNet::<P>::new();
+  let numeric = numeric_gradient(&mut nn, |nn| error(nn, &tset));
+  let step = first_step(&mut nn, |nn: &mut TestNeuralNet<P>| {
+    IRpropPlusTrainer::<_, _, TestParams, _>::with_epochs(nn, &tset, 1).last();
+  });
+
+  for (k, (s, g)) in step.iter().zip(numeric.iter()).enumerate() {
+    if g.abs() < 1e-8f64 { continue }
+
+    let expected = -g.signum() * TestParams::initial_step();
+
+    assert!(
+      (s - expected).abs() < 1e-12f64,
+      "weight {}: {} != {}", k, s, expected);
+  }
+}
+
+
+/// With a single output and member, the Jacobian has a single row, and a 
+/// heavily damped first step of Levenberg-Marquardt is that row, scaled by 
+/// the error over the damping factor.
+///
+fn check_levenberg_marquardt<P>() where P : NeuralNetParameters {
+  let tset = vec![(vec![0.3f64, -0.7f64, 0.5f64], vec![0.6f64])];
+  let mut nn = SingleOutputNeuralNet::<P>::new();
+  let numeric = numeric_gradient(&mut nn, |nn| {
+    nn.predict(&tset[0].0);
+    nn.output()[0]
+  });
+
+  nn.predict(&tset[0].0);
+
+  let e = tset[0].1[0] - nn.output()[0];
+  let step = first_step(&mut nn, |nn: &mut SingleOutputNeuralNet<P>| {
+    LevenbergMarquardtTrainer::<_, _, TestParams, _>::with_epochs(nn, &tset, 1).last();
+  });
+
+  for (k, (s, g)) in step.iter().zip(numeric.iter()).enumerate() {
+    let analytic = s * TestParams::initial_damping() / e;
+
+    assert!(
+      (analytic - g).abs() < 1e-4f64,
+      "weight {}: {} != {}", k, analytic, g);
+  }
+}
+
+
+/// The first step of scaled conjugate gradient and L-BFGS is along the 
+/// negative gradient, so it is the gradient scaled by some negative factor. 
+/// Scaled conjugate gradient doesn't move when its first step increases the 
+/// error, so its test parameters start with a large scale, and a short step.
+///
+fn check_descent<P, F>(train: F) 
+  where P : NeuralNetParameters,
+        F : FnOnce(&mut TestNeuralNet<P>, &[(Vec<f64>, Vec<f64>)])
+{
+  let tset = tset();
+  let mut nn = TestNeuralNet::<P>::new();
+  let numeric = numeric_gradient(&mut nn, |nn| error(nn, &tset));
+  let step = first_step(&mut nn, |nn: &mut TestNeuralNet<P>| train(nn, &tset));
+  let scale = -step.iter().zip(numeric.iter()).fold(0f64, |acc, (s, g)| acc + s * g) / 
+    numeric.iter().fold(0f64, |acc, g| acc + g * g);
+
+  assert!(scale > 0f64, "step should be downhill, but its scale is {}", scale);
+
+  for (k, (s, g)) in step.iter().zip(numeric.iter()).enumerate() {
+    let analytic = -s / scale;
+
+    assert!(
+      (analytic - g).abs() < 1e-6f64,
+      "weight {}: {} != {}", k, analytic, g);
+  }
+}
+
+
+#[test]
+fn quickprop_gradient_logistic() { check_quickprop::<LogisticNeuralNet>() }
+
+
+#[test]
+fn quickprop_gradient_tanh() { check_quickprop::<TanhNeuralNet>() }
+
+
+#[test]
+fn rprop_gradient_logistic() { check_rprop::<LogisticNeuralNet>() }
+
+
+#[test]
+fn rprop_gradient_tanh() { check_rprop::<TanhNeuralNet>() }
+
+
+#[test]
+fn levenberg_marquardt_jacobian_logistic() { 
+  check_levenberg_marquardt::<LogisticNeuralNet>() 
+}
+
+
+#[test]
+fn levenberg_marquardt_jacobian_tanh() { check_levenberg_marquardt::<TanhNeuralNet>() }
+
+
+#[test]
+fn quickprop_converges_tanh() {
+  let tset = tset();
+  let mut nn = TestNeuralNet::<TanhNeuralNet>::new();
+  let (_, err) = QuickpropTrainer::<_, _, TestParams, _>
+    ::with_epochs(&mut nn, &tset, 1000)
+    .last()
+    .unwrap();
+
+  assert!(err < 1e-4f64, "error {} should be near 0", err);
+}
+
+
+#[test]
+fn conjugate_gradient_gradient_logistic() {
+  check_descent::<LogisticNeuralNet, _>(|nn, tset| {
+    ScaledConjugateGradientTrainer::<_, _, TestParams, _>::with_epochs(nn, tset, 1).last();
+  })
+}
+
+
+#[test]
+fn conjugate_gradient_gradient_tanh() {
+  check_descent::<TanhNeuralNet, _>(|nn, tset| {
+    ScaledConjugateGradientTrainer::<_, _, TestParams, _>::with_epochs(nn, tset, 1).last();
+  })
+}
+
+
+#[test]
+fn conjugate_gradient_converges_tanh() {
+  let tset = tset();
+  let mut nn = TestNeuralNet::<TanhNeuralNet>::new();
+  let (_, err) = ScaledConjugateGradientTrainer::<_, _, TestParams, _>
+    ::with_epochs(&mut nn, &tset, 1000)
+    .last()
+    .unwrap();
+
+  assert!(err < 1e-4f64, "error {} should be near 0", err);
+}
+
+
+/// From these weights, the first steps of scaled conjugate gradient increase 
+/// the error, and are retried with a larger scale.
+///
+#[test]
+fn conjugate_gradient_recovers_from_failed_steps() {
+  let tset = tset();
+  let mut nn = TestNeuralNet::<TanhNeuralNet>::new();
+
+  for k in 0..weight_count::<TanhNeuralNet, TestNeuralNet<TanhNeuralNet>>() {
+    *nn.node_mut(weight::<TanhNeuralNet, TestNeuralNet<TanhNeuralNet>>(k)) = 
+      0.7f64 * (0.37f64 * k as f64).sin();
+  }
+
+  let (_, err) = ScaledConjugateGradientTrainer::<_, _, DefaultParams, _>
+    ::with_epochs(&mut nn, &tset, 1000)
+    .last()
+    .unwrap();
+
+  assert!(err < 1e-4f64, "error {} should be near 0", err);
+}
+
+
+#[test]
+fn lbfgs_gradient_logistic() {
+  check_descent::<LogisticNeuralNet, _>(|nn, tset| {
+    LbfgsTrainer::<_, _, TestParams, _>::with_epochs(nn, tset, 1).last();
+  })
+}
+
+
+#[test]
+fn lbfgs_gradient_tanh() {
+  check_descent::<TanhNeuralNet, _>(|nn, tset| {
+    LbfgsTrainer::<_, _, TestParams, _>::with_epochs(nn, tset, 1).last();
+  })
+}
+
+
+#[test]
+fn lbfgs_converges_tanh() {
+  let tset = tset();
+  let mut nn = TestNeuralNet::<TanhNeuralNet>::new();
+  let (_, err) = LbfgsTrainer::<_, _, TestParams, _>
+    ::with_epochs(&mut nn, &tset, 1000)
+    .last()
+    .unwrap();
+
+  assert!(err < 1e-4f64, "error {} should be near 0", err);
+}
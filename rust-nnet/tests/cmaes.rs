@@ -0,0 +1,49 @@
+extern crate nnet;
+#[macro_use(ffnn)] extern crate nnet_macros;
+
+use nnet::params::LogisticNeuralNet;
+use nnet::prelude::{NeuralNet, Node, CmaParameters};
+use nnet::trainer::cmaes::CmaEsTrainer;
+
+
+ffnn!(TestNeuralNet, 2, 2, 1);
+
+
+struct TestParams;
+
+impl CmaParameters for TestParams {
+  fn restarts() -> usize { 0 }
+}
+
+
+/// Negative ellipsoid over the weights of the neural net, with a condition
+/// number of 1e6. Its maximum is 0, when every weight is 0.
+///
+fn ellipsoid(nn: &mut TestNeuralNet<LogisticNeuralNet>) -> f64 {
+  let mut ws = Vec::new();
+
+  for i in 0..3 {
+    for j in 0..2 { ws.push(nn.node(Node::WeightInputHidden(i, j))); }
+  }
+
+  for i in 0..3 { ws.push(nn.node(Node::WeightHiddenOutput(i, 0))); }
+
+  let n = ws.len() - 1;
+
+  -ws.iter().enumerate().fold(0f64, |acc, (i, w)| {
+    acc + 10f64.powf(6f64 * i as f64 / n as f64) * w * w
+  })
+}
+
+
+#[test]
+fn cmaes_converges_on_ellipsoid() {
+  let mut nn = TestNeuralNet::<LogisticNeuralNet>::new();
+  let (_, best) = CmaEsTrainer::<_, _, TestParams, _>
+    ::with_fitness(&mut nn, ellipsoid, 1000)
+    .last()
+    .unwrap();
+
+  assert!(best > -1e-10f64, "best fitness {} should be near 0", best);
+  assert!(ellipsoid(&mut nn) > -1e-10f64);
+}
@@ -0,0 +1,51 @@
+extern crate nnet;
+#[macro_use(ffnn)] extern crate nnet_macros;
+
+use nnet::gradient::input_gradient;
+use nnet::params::{LogisticNeuralNet, TanhNeuralNet};
+use nnet::prelude::{Predictor, NeuralNetParameters};
+
+
+ffnn!(TestNeuralNet, 3, 4, 2);
+
+
+/// Compares the input gradient of each output to central differences of
+/// `predict`.
+///
+fn check<P>() where P : NeuralNetParameters {
+  let mut nn = TestNeuralNet::<P>::new();
+  let inp = [0.3f64, -0.7f64, 0.5f64];
+  let h = 1e-6f64;
+
+  for k in 0..2 {
+    let grad = input_gradient(&mut nn, &inp, k);
+
+    for i in 0..3 {
+      let mut hi = inp;
+      let mut lo = inp;
+
+      hi[i] += h;
+      lo[i] -= h;
+
+      nn.predict(&hi);
+      let fhi = nn.output()[k];
+
+      nn.predict(&lo);
+      let flo = nn.output()[k];
+
+      let numeric = (fhi - flo) / (2f64 * h);
+
+      assert!(
+        (grad[i] - numeric).abs() < 1e-6f64,
+        "output {}, input {}: {} != {}", k, i, grad[i], numeric);
+    }
+  }
+}
+
+
+#[test]
+fn input_gradient_logistic() { check::<LogisticNeuralNet>() }
+
+
+#[test]
+fn input_gradient_tanh() { check::<TanhNeuralNet>() }
@@ -0,0 +1,29 @@
+extern crate nnet;
+extern crate rand;
+
+use nnet::search::{Domain, SearchSpace};
+
+
+#[test]
+#[should_panic(expected = "should have values")]
+fn empty_discrete_domain_is_rejected() {
+  SearchSpace::new().param("hidden", Domain::Discrete(Vec::new()));
+}
+
+
+#[test]
+fn discrete_domain_is_sampled() {
+  let space = SearchSpace::new()
+    .param("hidden", Domain::Discrete(vec![4f64, 8f64]))
+    .param("lrate", Domain::Uniform(0.1f64, 0.5f64));
+  let mut rng = rand::thread_rng();
+
+  assert_eq!(space.grid(3).len(), 6);
+
+  for _ in 0..10 {
+    let candidate = space.sample(&mut rng);
+    let hidden = candidate.get("hidden");
+
+    assert!(hidden == 4f64 || hidden == 8f64);
+  }
+}